@@ -1,13 +1,8 @@
-use std::env;
-
 use deepgram::{auth::options::Options, Deepgram, DeepgramError};
 
 #[tokio::main]
 async fn main() -> Result<(), DeepgramError> {
-    let deepgram_api_key =
-        env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
-
-    let dg_client = Deepgram::new(&deepgram_api_key)?;
+    let dg_client = Deepgram::from_env()?;
 
     // Example 1: Generate a token with default 30-second TTL
     println!("Generating token with default TTL (30 seconds)...");
@@ -6,7 +6,6 @@ use deepgram::{speak::options::Options, Deepgram, DeepgramError};
 use futures::stream::StreamExt;
 use rodio::buffer::SamplesBuffer;
 use rodio::{OutputStream, Sink};
-use std::env;
 use std::time::Instant;
 
 #[derive(Clone)]
@@ -89,10 +88,7 @@ pub fn play_audio(sink: &Sink, sample_rate: u32, channels: u16, samples: Vec<i16
 
 #[tokio::main]
 async fn main() -> Result<(), DeepgramError> {
-    let deepgram_api_key =
-        env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
-
-    let dg_client = Deepgram::new(&deepgram_api_key)?;
+    let dg_client = Deepgram::from_env()?;
 
     let sample_rate = 16000;
     let channels = 1;
@@ -1,4 +1,3 @@
-use std::env;
 use std::io::Write;
 use std::thread;
 
@@ -73,10 +72,7 @@ fn microphone_as_stream() -> (FuturesReceiver<Result<Bytes, RecvError>>, u32) {
 
 #[tokio::main]
 async fn main() -> Result<(), DeepgramError> {
-    let deepgram_api_key =
-        env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
-
-    let dg_client = Deepgram::new(&deepgram_api_key)?;
+    let dg_client = Deepgram::from_env()?;
 
     // Configure Flux for more reliable turn detection
     // - eot_threshold: 0.75 (higher = more reliable, less false positives)
@@ -111,6 +107,7 @@ async fn main() -> Result<(), DeepgramError> {
             FluxResponse::Connected {
                 request_id,
                 sequence_id,
+                ..
             } => {
                 println!("✓ Connected: {} (seq: {})\n", request_id, sequence_id);
             }
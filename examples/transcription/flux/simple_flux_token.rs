@@ -6,7 +6,6 @@ Connected: 5add2b9f-42a7-406f-9fac-7ac4be9dc2cb (seq: 0)
 [Turn 0] UPDATE: Hello from Deepgram. Welcome to our voice AI APIs.I
 */
 
-use std::env;
 use std::io::Write;
 use std::time::Duration;
 
@@ -34,10 +33,7 @@ static FRAME_DELAY: Duration = Duration::from_millis(100);
 
 #[tokio::main]
 async fn main() -> Result<(), DeepgramError> {
-    let deepgram_api_key =
-        env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
-
-    let dg_client = Deepgram::new(&deepgram_api_key)?;
+    let dg_client = Deepgram::from_env()?;
     let token = dg_client.auth().grant(None).await?;
 
     let dg_client = Deepgram::with_temp_token(token.access_token)?;
@@ -69,6 +65,7 @@ async fn main() -> Result<(), DeepgramError> {
             FluxResponse::Connected {
                 request_id,
                 sequence_id,
+                ..
             } => {
                 println!("Connected: {} (seq: {})", request_id, sequence_id);
             }
@@ -12,10 +12,7 @@ static AUDIO_URL: &str = "https://static.deepgram.com/examples/Bueller-Life-move
 
 #[tokio::main]
 async fn main() -> Result<(), DeepgramError> {
-    let deepgram_api_key =
-        env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
-
-    let dg_client = Deepgram::new(&deepgram_api_key)?;
+    let dg_client = Deepgram::from_env()?;
 
     let source = AudioSource::from_url(AUDIO_URL);
 
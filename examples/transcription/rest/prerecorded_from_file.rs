@@ -1,5 +1,3 @@
-use std::env;
-
 use deepgram::{
     common::{
         audio_source::AudioSource,
@@ -13,10 +11,7 @@ static PATH_TO_FILE: &str = "examples/audio/bueller.wav";
 
 #[tokio::main]
 async fn main() -> Result<(), DeepgramError> {
-    let deepgram_api_key =
-        env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
-
-    let dg_client = Deepgram::new(&deepgram_api_key)?;
+    let dg_client = Deepgram::from_env()?;
 
     let file = File::open(PATH_TO_FILE).await.unwrap();
 
@@ -1,4 +1,3 @@
-use std::env;
 use std::thread;
 
 use bytes::{BufMut, Bytes, BytesMut};
@@ -76,10 +75,7 @@ fn microphone_as_stream() -> FuturesReceiver<Result<Bytes, RecvError>> {
 
 #[tokio::main]
 async fn main() -> Result<(), DeepgramError> {
-    let deepgram_api_key =
-        env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
-
-    let dg_client = Deepgram::new(&deepgram_api_key)?;
+    let dg_client = Deepgram::from_env()?;
 
     let mut results = dg_client
         .transcription()
@@ -1,4 +1,3 @@
-use std::env;
 use std::time::Duration;
 
 use futures::stream::StreamExt;
@@ -14,10 +13,7 @@ static FRAME_DELAY: Duration = Duration::from_millis(16);
 
 #[tokio::main]
 async fn main() -> Result<(), DeepgramError> {
-    let deepgram_api_key =
-        env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
-
-    let dg_client = Deepgram::new(&deepgram_api_key)?;
+    let dg_client = Deepgram::from_env()?;
 
     let options = Options::builder()
         .smart_format(true)
@@ -109,12 +109,13 @@ impl Auth<'_> {
 
         let request = if let Some(opts) = options {
             self.0
+                .inner
                 .client
                 .post(url)
                 .json(&SerializableOptions::from(opts))
         } else {
             // Send empty JSON object when no options provided
-            self.0.client.post(url).json(&serde_json::json!({}))
+            self.0.inner.client.post(url).json(&serde_json::json!({}))
         };
 
         send_and_translate_response(request).await
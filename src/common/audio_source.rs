@@ -4,6 +4,8 @@
 //!
 //! [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
 
+use std::fmt;
+
 use reqwest::{header::CONTENT_TYPE, RequestBuilder};
 use serde::Serialize;
 
@@ -34,6 +36,10 @@ impl AudioSource {
     ///
     /// Use [`AudioSource::from_buffer_with_mime_type`] if you want to specify a [MIME type][mime].
     ///
+    /// A buffer held in memory that starts with a WAV header is sent as
+    /// `audio/wav`, and without the `encoding`, `sample_rate` and `channels`
+    /// parameters, which only apply to raw audio. See [`MimeType::detect`].
+    ///
     /// [mime]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types#audio_and_video_types
     pub fn from_buffer(buffer: impl Into<reqwest::Body>) -> Self {
         Self(InternalAudioSource::Buffer {
@@ -44,6 +50,8 @@ impl AudioSource {
 
     /// Same as [`AudioSource::from_buffer`], but allows you to specify a [MIME type][mime].
     ///
+    /// The MIME type is used unless the buffer is detected as WAV audio.
+    ///
     /// [mime]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types#audio_and_video_types
     pub fn from_buffer_with_mime_type(
         buffer: impl Into<reqwest::Body>,
@@ -55,6 +63,31 @@ impl AudioSource {
         })
     }
 
+    /// Check that the source can be sent to Deepgram.
+    #[cfg(feature = "listen")]
+    pub(crate) fn validate(&self) -> crate::Result<()> {
+        match &self.0 {
+            InternalAudioSource::Buffer {
+                mime_type: Some(mime_type),
+                ..
+            } if mime_type.trim().is_empty() => Err(crate::DeepgramError::InvalidOptions(
+                "the MIME type of the audio must not be empty".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether the audio is held in memory and starts with a WAV header.
+    #[cfg(feature = "listen")]
+    pub(crate) fn is_wav(&self) -> bool {
+        match &self.0 {
+            InternalAudioSource::Buffer { buffer, .. } => {
+                buffer.as_bytes().and_then(MimeType::detect) == Some(MimeType::wav())
+            }
+            InternalAudioSource::Url(_) => false,
+        }
+    }
+
     #[allow(missing_docs)]
     pub fn fill_body(self, request_builder: RequestBuilder) -> RequestBuilder {
         match self.0 {
@@ -67,6 +100,11 @@ impl AudioSource {
                 request_builder.json(&UrlSource { url })
             }
             InternalAudioSource::Buffer { buffer, mime_type } => {
+                let mime_type = buffer
+                    .as_bytes()
+                    .and_then(MimeType::detect)
+                    .map(String::from)
+                    .or(mime_type);
                 let request_builder = request_builder.body(buffer);
 
                 if let Some(mime_type) = mime_type {
@@ -78,3 +116,136 @@ impl AudioSource {
         }
     }
 }
+
+/// The [MIME type][mime] of common audio containers, sent as the
+/// `Content-Type` of pre-recorded audio.
+///
+/// Can be passed wherever a MIME type is expected, such as
+/// [`AudioSource::from_buffer_with_mime_type`] or
+/// [`Transcription::prerecorded_buffer`](crate::Transcription::prerecorded_buffer).
+///
+/// ```
+/// use deepgram::common::audio_source::{AudioSource, MimeType};
+///
+/// let audio = std::fs::read("Bueller-Life-moves-pretty-fast.wav").unwrap_or_default();
+/// let source = AudioSource::from_buffer_with_mime_type(audio, MimeType::wav());
+/// ```
+///
+/// [mime]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types#audio_and_video_types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MimeType(&'static str);
+
+impl MimeType {
+    /// WAV audio, `audio/wav`.
+    pub fn wav() -> Self {
+        Self("audio/wav")
+    }
+
+    /// MP3 audio, `audio/mpeg`.
+    pub fn mp3() -> Self {
+        Self("audio/mpeg")
+    }
+
+    /// MP4 or M4A audio, `audio/mp4`.
+    pub fn mp4() -> Self {
+        Self("audio/mp4")
+    }
+
+    /// AAC audio, `audio/aac`.
+    pub fn aac() -> Self {
+        Self("audio/aac")
+    }
+
+    /// FLAC audio, `audio/flac`.
+    pub fn flac() -> Self {
+        Self("audio/flac")
+    }
+
+    /// Opus audio in an Ogg container, `audio/ogg`.
+    pub fn ogg_opus() -> Self {
+        Self("audio/ogg")
+    }
+
+    /// Audio in a WebM container, `audio/webm`.
+    pub fn webm() -> Self {
+        Self("audio/webm")
+    }
+
+    /// Detect the MIME type of audio from its first bytes.
+    ///
+    /// Only WAV audio, whose RIFF header describes its encoding, is
+    /// recognized. Returns `None` for anything else, such as raw PCM.
+    ///
+    /// ```
+    /// use deepgram::common::audio_source::MimeType;
+    ///
+    /// assert_eq!(MimeType::detect(b"RIFF\x24\x08\x00\x00WAVEfmt "), Some(MimeType::wav()));
+    /// assert_eq!(MimeType::detect(&[0; 16]), None);
+    /// ```
+    pub fn detect(header: &[u8]) -> Option<Self> {
+        let riff = header.starts_with(b"RIFF") || header.starts_with(b"RF64");
+        (riff && header.get(8..12) == Some(b"WAVE")).then(Self::wav)
+    }
+
+    /// The MIME type as a string.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl AsRef<str> for MimeType {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl From<MimeType> for String {
+    fn from(mime_type: MimeType) -> Self {
+        mime_type.0.to_string()
+    }
+}
+
+impl fmt::Display for MimeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MimeType;
+
+    #[test]
+    fn mime_types() {
+        for (mime_type, expected) in [
+            (MimeType::wav(), "audio/wav"),
+            (MimeType::mp3(), "audio/mpeg"),
+            (MimeType::mp4(), "audio/mp4"),
+            (MimeType::aac(), "audio/aac"),
+            (MimeType::flac(), "audio/flac"),
+            (MimeType::ogg_opus(), "audio/ogg"),
+            (MimeType::webm(), "audio/webm"),
+        ] {
+            assert_eq!(mime_type.as_str(), expected);
+            assert_eq!(String::from(mime_type), expected);
+        }
+    }
+
+    #[test]
+    fn detect() {
+        let wav = b"RIFF\x2c\x00\x00\x00WAVEfmt ";
+        assert_eq!(MimeType::detect(wav), Some(MimeType::wav()));
+        assert_eq!(
+            MimeType::detect(b"RF64\xff\xff\xff\xffWAVEds64"),
+            Some(MimeType::wav())
+        );
+
+        // Other RIFF forms, truncated headers and raw PCM are not WAV.
+        assert_eq!(MimeType::detect(b"RIFF\x2c\x00\x00\x00AVI LIST"), None);
+        assert_eq!(MimeType::detect(&wav[..10]), None);
+        assert_eq!(
+            MimeType::detect(&[0, 0, 0xff, 0x7f, 0, 0x80, 0, 0, 0, 0, 0, 0]),
+            None
+        );
+    }
+}
@@ -4,6 +4,8 @@
 //!
 //! [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded-responses
 
+use std::collections::BTreeSet;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -22,6 +24,72 @@ pub struct Response {
     pub results: ListenResults,
 }
 
+impl Response {
+    /// The whole transcript as plain text.
+    ///
+    /// Only the top alternative of each channel is used, with one channel
+    /// per line. When the [Paragraphs feature][docs] is set, the paragraph
+    /// breaks are kept.
+    ///
+    /// [docs]: https://developers.deepgram.com/docs/paragraphs
+    pub fn to_transcript(&self) -> String {
+        self.top_alternatives()
+            .map(|alternative| match &alternative.paragraphs {
+                Some(paragraphs) => paragraphs.transcript.trim(),
+                None => alternative.transcript.trim(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The transcript as formatted by Deepgram, one channel per line.
+    ///
+    /// Only the top alternative of each channel is used. With the
+    /// [Dictation feature][dictation] spoken punctuation such as "comma" is
+    /// replaced by the symbol, and with the [Measurements feature][measurements]
+    /// units are abbreviated. Unlike [`Response::to_transcript`], paragraph
+    /// breaks are not kept.
+    ///
+    /// [dictation]: https://developers.deepgram.com/docs/dictation
+    /// [measurements]: https://developers.deepgram.com/docs/measurements
+    pub fn formatted_text(&self) -> String {
+        self.top_alternatives()
+            .map(|alternative| alternative.transcript.trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The words as they were recognized, without any formatting, one
+    /// channel per line.
+    ///
+    /// Only the top alternative of each channel is used. Punctuation,
+    /// capitalization and the symbols of the [Dictation feature][dictation]
+    /// are left out, so this is the text to compare against other
+    /// recognizers or to search.
+    ///
+    /// [dictation]: https://developers.deepgram.com/docs/dictation
+    pub fn raw_text(&self) -> String {
+        self.top_alternatives()
+            .map(|alternative| {
+                alternative
+                    .words
+                    .iter()
+                    .map(|word| word.word.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn top_alternatives(&self) -> impl Iterator<Item = &ResultAlternative> {
+        self.results
+            .channels
+            .iter()
+            .filter_map(|channel| channel.alternatives.first())
+    }
+}
+
 /// Returned by [`Transcription::prerecorded_callback`](crate::Transcription::prerecorded_callback).
 ///
 /// See the [Deepgram Callback feature docs][docs] for more info.
@@ -89,7 +157,9 @@ pub struct ListenResults {
     #[allow(missing_docs)]
     pub topics: Option<Topics>,
 
-    #[allow(missing_docs)]
+    /// [`None`] unless the [Summarize feature][docs] is set.
+    ///
+    /// [docs]: https://developers.deepgram.com/docs/summarization
     pub summary: Option<Summary>,
 }
 
@@ -174,19 +244,33 @@ pub struct SearchResults {
 
 /// Sentence
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Sentence {
-    text: String,
-    start: f64,
-    end: f64,
+    #[allow(missing_docs)]
+    pub text: String,
+
+    #[allow(missing_docs)]
+    pub start: f64,
+
+    #[allow(missing_docs)]
+    pub end: f64,
 }
 
 /// Paragraph
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Paragraph {
-    sentences: Vec<Sentence>,
-    num_words: usize,
-    start: f64,
-    end: f64,
+    #[allow(missing_docs)]
+    pub sentences: Vec<Sentence>,
+
+    #[allow(missing_docs)]
+    pub num_words: usize,
+
+    #[allow(missing_docs)]
+    pub start: f64,
+
+    #[allow(missing_docs)]
+    pub end: f64,
 }
 
 /// Paragraph results.
@@ -197,41 +281,71 @@ pub struct Paragraph {
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
 /// [docs]: https://developers.deepgram.com/docs/paragraphs
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Paragraphs {
-    transcript: String,
-    paragraphs: Vec<Paragraph>,
+    #[allow(missing_docs)]
+    pub transcript: String,
+
+    #[allow(missing_docs)]
+    pub paragraphs: Vec<Paragraph>,
 }
 
 /// Entity Detection results.
 ///
 /// See the [Deepgram API Reference][api]
-/// and the [Deepgram Search feature docs][docs] for more info.
+/// and the [Deepgram Entity Detection feature docs][docs] for more info.
 ///
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
 /// [docs]: https://developers.deepgram.com/docs/detect-entities
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Entity {
-    label: String,
-    value: String,
-    confidence: f64,
-    start_word: usize,
-    end_word: usize,
+    /// The type of entity, e.g. `"NAME"` or `"PHONE_NUMBER"`.
+    pub label: String,
+
+    /// The entity as it appears in the transcript.
+    pub value: String,
+
+    #[allow(missing_docs)]
+    pub confidence: f64,
+
+    /// Index of the first word of the entity in [`ResultAlternative::words`].
+    pub start_word: usize,
+
+    /// Index one past the last word of the entity in [`ResultAlternative::words`].
+    pub end_word: usize,
 }
 
-/// Intent
+/// An intent recognized in a segment of the transcript.
+///
+/// See the [Deepgram Intent Recognition feature docs][docs] for more info.
+///
+/// [docs]: https://developers.deepgram.com/docs/intent-recognition
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Intent {
-    intent: String,
-    confidence_score: f64,
+    /// The intent as a short phrase, such as `"Cancel subscription"`.
+    pub intent: String,
+
+    #[allow(missing_docs)]
+    pub confidence_score: f64,
 }
 
-/// Segment
+/// A segment of the transcript and the intents recognized in it.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Segment {
-    text: String,
-    start_word: usize,
-    end_word: usize,
-    intents: Vec<Intent>,
+    #[allow(missing_docs)]
+    pub text: String,
+
+    /// Index of the first word of the segment in [`ResultAlternative::words`].
+    pub start_word: usize,
+
+    /// Index one past the last word of the segment in [`ResultAlternative::words`].
+    pub end_word: usize,
+
+    #[allow(missing_docs)]
+    pub intents: Vec<Intent>,
 }
 
 /// Intent Recognition results.
@@ -242,27 +356,97 @@ pub struct Segment {
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
 /// [docs]: https://developers.deepgram.com/docs/intent-recognition
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Intents {
-    segments: Vec<Segment>,
+    /// The segments of the transcript in which intents were recognized.
+    pub segments: Vec<Segment>,
 }
 
-/// SentimentSegment
+impl Intents {
+    /// The intent recognized with the highest confidence across all
+    /// segments, or [`None`] if there are none.
+    pub fn top_intent(&self) -> Option<&Intent> {
+        self.segments
+            .iter()
+            .flat_map(|segment| &segment.intents)
+            .max_by(|a, b| a.confidence_score.total_cmp(&b.confidence_score))
+    }
+}
+
+/// Whether a sentiment is positive, neutral or negative.
+///
+/// See the [Deepgram Sentiment Analysis feature docs][docs] for more info.
+///
+/// [docs]: https://developers.deepgram.com/docs/sentiment-analysis
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum SentimentLabel {
+    #[allow(missing_docs)]
+    Positive,
+
+    #[allow(missing_docs)]
+    Neutral,
+
+    #[allow(missing_docs)]
+    Negative,
+}
+
+impl SentimentLabel {
+    /// The label Deepgram gives to a sentiment score between -1 and 1.
+    ///
+    /// Scores above 0.333 are positive, scores below -0.333 are negative, and
+    /// scores in between are neutral.
+    pub fn from_score(score: f64) -> Self {
+        if score > 0.333 {
+            Self::Positive
+        } else if score < -0.333 {
+            Self::Negative
+        } else {
+            Self::Neutral
+        }
+    }
+}
+
+/// The sentiment of a word, a segment or a whole transcript.
+///
+/// See the [Deepgram Sentiment Analysis feature docs][docs] for more info.
+///
+/// [docs]: https://developers.deepgram.com/docs/sentiment-analysis
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
-pub struct SentimentSegment {
-    text: String,
-    start_word: usize,
-    end_word: usize,
-    sentiment: String,
-    sentiment_score: f64,
+#[non_exhaustive]
+pub struct Sentiment {
+    #[allow(missing_docs)]
+    pub sentiment: SentimentLabel,
+
+    /// From -1, most negative, to 1, most positive.
+    pub sentiment_score: f64,
 }
 
-/// SentimentAverage
+/// A run of words sharing the same sentiment.
+///
+/// See [`Sentiments::segments`] and [`ResultAlternative::sentiment_segments`].
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
-pub struct SentimentAverage {
-    sentiment: String,
-    sentiment_score: f64,
+#[non_exhaustive]
+pub struct SentimentSegment {
+    #[allow(missing_docs)]
+    pub text: String,
+
+    /// Index of the first word of the segment in [`ResultAlternative::words`].
+    pub start_word: usize,
+
+    /// Index one past the last word of the segment in [`ResultAlternative::words`].
+    pub end_word: usize,
+
+    #[allow(missing_docs)]
+    #[serde(flatten)]
+    pub sentiment: Sentiment,
 }
 
+/// The average sentiment of a transcript.
+#[deprecated(since = "0.8.0", note = "Use Sentiment instead.")]
+pub type SentimentAverage = Sentiment;
+
 /// Sentiment Analysis results.
 ///
 /// See the [Deepgram API Reference][api]
@@ -271,25 +455,49 @@ pub struct SentimentAverage {
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
 /// [docs]: https://developers.deepgram.com/docs/sentiment-analysis
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Sentiments {
-    segments: Vec<SentimentSegment>,
-    average: SentimentAverage,
+    /// The transcript split where its sentiment changes.
+    pub segments: Vec<SentimentSegment>,
+
+    /// The sentiment of the transcript as a whole.
+    pub average: Sentiment,
 }
 
-/// TopicDetail
+/// A topic detected in a segment of the transcript.
+///
+/// See the [Deepgram Topic Detection feature docs][docs] for more info.
+///
+/// [docs]: https://developers.deepgram.com/docs/topic-detection
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
-pub struct TopicDetail {
-    topic: String,
-    confidence_score: f64,
+#[non_exhaustive]
+pub struct Topic {
+    /// The topic as a short phrase, such as `"Customer service"`.
+    pub topic: String,
+
+    #[allow(missing_docs)]
+    pub confidence_score: f64,
 }
 
-/// TopicSegment
+/// A topic detected in a segment of the transcript.
+#[deprecated(since = "0.8.0", note = "Use Topic instead.")]
+pub type TopicDetail = Topic;
+
+/// A segment of the transcript and the topics detected in it.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct TopicSegment {
-    text: String,
-    start_word: usize,
-    end_word: usize,
-    topics: Vec<TopicDetail>,
+    #[allow(missing_docs)]
+    pub text: String,
+
+    /// Index of the first word of the segment in [`ResultAlternative::words`].
+    pub start_word: usize,
+
+    /// Index one past the last word of the segment in [`ResultAlternative::words`].
+    pub end_word: usize,
+
+    #[allow(missing_docs)]
+    pub topics: Vec<Topic>,
 }
 
 /// Topics Detection results.
@@ -300,21 +508,38 @@ pub struct TopicSegment {
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
 /// [docs]: https://developers.deepgram.com/docs/topic-detection
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Topics {
-    segments: Vec<TopicSegment>,
+    /// The segments of the transcript in which topics were detected.
+    pub segments: Vec<TopicSegment>,
+}
+
+impl Topics {
+    /// Every topic detected in any segment, once each, in alphabetical order.
+    pub fn topics_set(&self) -> BTreeSet<&str> {
+        self.segments
+            .iter()
+            .flat_map(|segment| &segment.topics)
+            .map(|topic| topic.topic.as_str())
+            .collect()
+    }
 }
 
 /// Summary results.
 ///
 /// See the [Deepgram API Reference][api]
-/// and the [Deepgram Search feature docs][docs] for more info.
+/// and the [Deepgram Summarize feature docs][docs] for more info.
 ///
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
 /// [docs]: https://developers.deepgram.com/docs/summarization
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct Summary {
-    result: String,
-    short: String,
+    /// Whether the summary succeeded, e.g. `"success"`.
+    pub result: String,
+
+    /// The summary text.
+    pub short: String,
 }
 
 /// Transcript alternatives.
@@ -337,12 +562,66 @@ pub struct ResultAlternative {
     #[allow(missing_docs)]
     pub paragraphs: Option<Paragraphs>,
 
-    #[allow(missing_docs)]
+    /// [`None`] unless the [Entity Detection feature][docs] is set.
+    ///
+    /// [docs]: https://developers.deepgram.com/docs/detect-entities
+    #[serde(default)]
     pub entities: Option<Vec<Entity>>,
 
     #[allow(missing_docs)]
     #[serde(default)]
     pub languages: Vec<String>,
+
+    /// [`None`] unless the [Summarize feature][docs] is set and the summary
+    /// is returned for this alternative.
+    ///
+    /// [docs]: https://developers.deepgram.com/docs/summarization
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<Summary>,
+}
+
+impl ResultAlternative {
+    /// Group the words into runs of consecutive words with the same
+    /// sentiment label, in order.
+    ///
+    /// The score of each segment is the mean score of its words. Words
+    /// without a sentiment, as when the [Sentiment Analysis feature][docs]
+    /// is not set, are left out.
+    ///
+    /// [docs]: https://developers.deepgram.com/docs/sentiment-analysis
+    pub fn sentiment_segments(&self) -> Vec<SentimentSegment> {
+        let tagged: Vec<(usize, &Word, &Sentiment)> = self
+            .words
+            .iter()
+            .enumerate()
+            .filter_map(|(index, word)| Some((index, word, word.sentiment.as_ref()?)))
+            .collect();
+
+        tagged
+            .chunk_by(|(a, _, a_sentiment), (b, _, b_sentiment)| {
+                *b == a + 1 && a_sentiment.sentiment == b_sentiment.sentiment
+            })
+            .map(|run| {
+                let text: Vec<_> = run
+                    .iter()
+                    .map(|(_, word, _)| word.punctuated_word.as_deref().unwrap_or(&word.word))
+                    .collect();
+                let total: f64 = run
+                    .iter()
+                    .map(|(_, _, sentiment)| sentiment.sentiment_score)
+                    .sum();
+                SentimentSegment {
+                    text: text.join(" "),
+                    start_word: run[0].0,
+                    end_word: run[run.len() - 1].0 + 1,
+                    sentiment: Sentiment {
+                        sentiment: run[0].2.sentiment,
+                        sentiment_score: total / run.len() as f64,
+                    },
+                }
+            })
+            .collect()
+    }
 }
 
 /// A single transcribed word.
@@ -374,6 +653,19 @@ pub struct Word {
     ///
     /// [docs]: https://developers.deepgram.com/documentation/features/punctuate/
     pub punctuated_word: Option<String>,
+
+    /// [`None`] unless the [Sentiment Analysis feature][docs] is set.
+    ///
+    /// [docs]: https://developers.deepgram.com/docs/sentiment-analysis
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    pub sentiment: Option<Sentiment>,
+
+    /// The language the word was spoken in.
+    ///
+    /// [`None`] unless transcribing with
+    /// [`Language::multi`](crate::common::options::Language::multi).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Search result.
@@ -398,3 +690,365 @@ pub struct Hit {
     #[allow(missing_docs)]
     pub snippet: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Intents, Response, SentimentLabel, Word};
+
+    const METADATA: &str = r#"{
+        "request_id": "00000000-0000-4000-8000-000000000000",
+        "transaction_key": "deprecated",
+        "sha256": "6d3c8b7a",
+        "created": "2025-01-01T00:00:00.000Z",
+        "duration": 1.5,
+        "channels": 1
+    }"#;
+
+    fn response(alternative: &str, extra_results: &str) -> Response {
+        serde_json::from_str(&format!(
+            r#"{{
+                "metadata": {METADATA},
+                "results": {{
+                    "channels": [{{
+                        "alternatives": [{{
+                            "transcript": "hello world",
+                            "confidence": 0.98,
+                            "words": []
+                            {alternative}
+                        }}]
+                    }}]
+                    {extra_results}
+                }}
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn word_language() {
+        let words: Vec<Word> = serde_json::from_str(
+            r#"[
+                {"word": "hello", "start": 0.0, "end": 0.5, "confidence": 0.9, "language": "en"},
+                {"word": "amigo", "start": 0.5, "end": 1.0, "confidence": 0.9, "language": "es"}
+            ]"#,
+        )
+        .unwrap();
+        let languages: Vec<_> = words.iter().map(|word| word.language.as_deref()).collect();
+        assert_eq!(languages, [Some("en"), Some("es")]);
+
+        let word: Word = serde_json::from_str(
+            r#"{"word": "hello", "start": 0.0, "end": 0.5, "confidence": 0.9}"#,
+        )
+        .unwrap();
+        assert_eq!(word.language, None);
+        assert!(!serde_json::to_string(&word).unwrap().contains("language"));
+    }
+
+    #[test]
+    fn summary() {
+        let response = response(
+            r#", "summary": {"result": "success", "short": "A greeting."}"#,
+            r#", "summary": {"result": "success", "short": "Someone says hello."}"#,
+        );
+
+        let summary = response.results.summary.unwrap();
+        assert_eq!(summary.result, "success");
+        assert_eq!(summary.short, "Someone says hello.");
+
+        let alternative = &response.results.channels[0].alternatives[0];
+        assert_eq!(alternative.summary.as_ref().unwrap().short, "A greeting.");
+    }
+
+    #[test]
+    fn summary_absent() {
+        let response = response("", "");
+        assert_eq!(response.results.summary, None);
+        assert_eq!(response.results.channels[0].alternatives[0].summary, None);
+    }
+
+    #[test]
+    fn entities() {
+        let without = response("", "");
+        assert_eq!(without.results.channels[0].alternatives[0].entities, None);
+
+        let response = response(
+            r#", "entities": [{
+                "label": "NAME",
+                "value": "Ferris",
+                "confidence": 0.95,
+                "start_word": 1,
+                "end_word": 2
+            }]"#,
+            "",
+        );
+
+        let entities = response.results.channels[0].alternatives[0]
+            .entities
+            .as_ref()
+            .unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].label, "NAME");
+        assert_eq!(entities[0].value, "Ferris");
+        assert_eq!(entities[0].confidence, 0.95);
+        assert_eq!((entities[0].start_word, entities[0].end_word), (1, 2));
+    }
+
+    #[test]
+    fn dictation_and_measurements() {
+        let response: Response = serde_json::from_str(&format!(
+            r#"{{
+                "metadata": {METADATA},
+                "results": {{
+                    "channels": [{{
+                        "alternatives": [{{
+                            "transcript": "Ship 5 kg, please.",
+                            "confidence": 0.98,
+                            "words": [
+                                {{"word": "ship", "start": 0.0, "end": 0.25, "confidence": 0.99, "punctuated_word": "Ship"}},
+                                {{"word": "five", "start": 0.25, "end": 0.5, "confidence": 0.98, "punctuated_word": "5"}},
+                                {{"word": "kilograms", "start": 0.5, "end": 1.0, "confidence": 0.97, "punctuated_word": "kg,"}},
+                                {{"word": "please", "start": 1.25, "end": 1.5, "confidence": 0.99, "punctuated_word": "please."}}
+                            ]
+                        }}]
+                    }}]
+                }}
+            }}"#
+        ))
+        .unwrap();
+
+        assert_eq!(response.formatted_text(), "Ship 5 kg, please.");
+        assert_eq!(response.raw_text(), "ship five kilograms please");
+    }
+
+    #[test]
+    fn sentiments() {
+        let word = |word: &str, start: f64, sentiment: &str, score: f64| {
+            format!(
+                r#"{{"word": "{word}", "start": {start}, "end": {}, "confidence": 0.9,
+                    "sentiment": "{sentiment}", "sentiment_score": {score}}}"#,
+                start + 0.5
+            )
+        };
+        let words = [
+            word("i", 0.0, "neutral", 0.0),
+            word("love", 0.5, "positive", 0.75),
+            word("rust", 1.0, "positive", 0.5),
+            word("but", 1.5, "neutral", 0.125),
+            word("hate", 2.0, "negative", -0.8),
+        ]
+        .join(", ");
+        let response: Response = serde_json::from_str(&format!(
+            r#"{{
+                "metadata": {METADATA},
+                "results": {{
+                    "channels": [{{
+                        "alternatives": [{{
+                            "transcript": "i love rust but hate",
+                            "confidence": 0.9,
+                            "words": [{words}]
+                        }}]
+                    }}],
+                    "sentiments": {{
+                        "segments": [{{
+                            "text": "i love rust but hate",
+                            "start_word": 0,
+                            "end_word": 5,
+                            "sentiment": "positive",
+                            "sentiment_score": 0.4
+                        }}],
+                        "average": {{"sentiment": "neutral", "sentiment_score": 0.1}}
+                    }}
+                }}
+            }}"#
+        ))
+        .unwrap();
+
+        let sentiments = response.results.sentiments.as_ref().unwrap();
+        assert_eq!(sentiments.average.sentiment, SentimentLabel::Neutral);
+        assert_eq!(sentiments.average.sentiment_score, 0.1);
+        assert_eq!(sentiments.segments.len(), 1);
+        assert_eq!(
+            sentiments.segments[0].sentiment.sentiment,
+            SentimentLabel::Positive
+        );
+        assert_eq!(
+            (
+                sentiments.segments[0].start_word,
+                sentiments.segments[0].end_word
+            ),
+            (0, 5)
+        );
+
+        let alternative = &response.results.channels[0].alternatives[0];
+        let segments: Vec<_> = alternative
+            .sentiment_segments()
+            .into_iter()
+            .map(|segment| {
+                (
+                    segment.text,
+                    segment.start_word..segment.end_word,
+                    segment.sentiment.sentiment,
+                    segment.sentiment.sentiment_score,
+                )
+            })
+            .collect();
+        assert_eq!(
+            segments,
+            [
+                ("i".to_string(), 0..1, SentimentLabel::Neutral, 0.0),
+                (
+                    "love rust".to_string(),
+                    1..3,
+                    SentimentLabel::Positive,
+                    0.625
+                ),
+                ("but".to_string(), 3..4, SentimentLabel::Neutral, 0.125),
+                ("hate".to_string(), 4..5, SentimentLabel::Negative, -0.8),
+            ]
+        );
+
+        let round_trip: Response =
+            serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+        assert_eq!(round_trip, response);
+
+        // Without the feature, words carry no sentiment.
+        let without = self::response("", "");
+        assert_eq!(without.results.sentiments, None);
+        assert!(without.results.channels[0].alternatives[0]
+            .sentiment_segments()
+            .is_empty());
+    }
+
+    #[test]
+    fn intents() {
+        let response = response(
+            "",
+            r#", "intents": {"segments": [
+                {
+                    "text": "I want to cancel my order.",
+                    "start_word": 0,
+                    "end_word": 6,
+                    "intents": [
+                        {"intent": "Cancel order", "confidence_score": 0.875},
+                        {"intent": "Request refund", "confidence_score": 0.25}
+                    ]
+                },
+                {
+                    "text": "And get my money back.",
+                    "start_word": 6,
+                    "end_word": 11,
+                    "intents": [{"intent": "Request refund", "confidence_score": 0.5}]
+                }
+            ]}"#,
+        );
+
+        let intents = response.results.intents.as_ref().unwrap();
+        assert_eq!(intents.segments.len(), 2);
+        assert_eq!(intents.segments[1].text, "And get my money back.");
+        assert_eq!(
+            (intents.segments[1].start_word, intents.segments[1].end_word),
+            (6, 11)
+        );
+        assert_eq!(intents.segments[0].intents[1].intent, "Request refund");
+
+        let top = intents.top_intent().unwrap();
+        assert_eq!(top.intent, "Cancel order");
+        assert_eq!(top.confidence_score, 0.875);
+
+        let empty = Intents {
+            segments: Vec::new(),
+        };
+        assert_eq!(empty.top_intent(), None);
+        assert_eq!(self::response("", "").results.intents, None);
+    }
+
+    #[test]
+    fn topics() {
+        let response = response(
+            "",
+            r#", "topics": {"segments": [
+                {
+                    "text": "My order arrived broken.",
+                    "start_word": 0,
+                    "end_word": 4,
+                    "topics": [
+                        {"topic": "Shipping", "confidence_score": 0.75},
+                        {"topic": "Product quality", "confidence_score": 0.5}
+                    ]
+                },
+                {
+                    "text": "The courier was late too.",
+                    "start_word": 4,
+                    "end_word": 9,
+                    "topics": [{"topic": "Shipping", "confidence_score": 0.875}]
+                }
+            ]}"#,
+        );
+
+        let topics = response.results.topics.as_ref().unwrap();
+        assert_eq!(topics.segments.len(), 2);
+        assert_eq!(topics.segments[0].text, "My order arrived broken.");
+        assert_eq!(
+            (topics.segments[1].start_word, topics.segments[1].end_word),
+            (4, 9)
+        );
+        assert_eq!(topics.segments[1].topics[0].confidence_score, 0.875);
+        assert_eq!(
+            topics.topics_set().into_iter().collect::<Vec<_>>(),
+            ["Product quality", "Shipping"]
+        );
+        assert_eq!(self::response("", "").results.topics, None);
+    }
+
+    #[test]
+    fn sentiment_label_from_score() {
+        for (score, label) in [
+            (1.0, SentimentLabel::Positive),
+            (0.334, SentimentLabel::Positive),
+            (0.333, SentimentLabel::Neutral),
+            (0.0, SentimentLabel::Neutral),
+            (-0.333, SentimentLabel::Neutral),
+            (-0.334, SentimentLabel::Negative),
+            (-1.0, SentimentLabel::Negative),
+        ] {
+            assert_eq!(SentimentLabel::from_score(score), label, "{score}");
+        }
+    }
+
+    #[test]
+    fn to_transcript() {
+        let response: Response = serde_json::from_str(&format!(
+            r#"{{
+                "metadata": {METADATA},
+                "results": {{
+                    "channels": [
+                        {{
+                            "alternatives": [
+                                {{"transcript": "hello world", "confidence": 0.98, "words": []}},
+                                {{"transcript": "yellow word", "confidence": 0.41, "words": []}}
+                            ]
+                        }},
+                        {{
+                            "alternatives": [{{
+                                "transcript": "hi there how are you",
+                                "confidence": 0.95,
+                                "words": [],
+                                "paragraphs": {{
+                                    "transcript": "\nHi there.\n\nHow are you?",
+                                    "paragraphs": []
+                                }}
+                            }}]
+                        }}
+                    ]
+                }}
+            }}"#
+        ))
+        .unwrap();
+
+        assert_eq!(
+            response.to_transcript(),
+            "hello world\nHi there.\n\nHow are you?"
+        );
+        assert_eq!(self::response("", "").to_transcript(), "hello world");
+    }
+}
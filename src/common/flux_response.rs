@@ -4,7 +4,10 @@
 //!
 //! [api]: https://developers.deepgram.com/reference/speech-to-text/listen-flux
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
 
 /// Flux WebSocket message types
@@ -19,6 +22,11 @@ pub enum FluxResponse {
 
         #[allow(missing_docs)]
         sequence_id: u32,
+
+        /// Any other fields of the message, such as the parameters some
+        /// endpoints echo back as applied.
+        #[serde(flatten)]
+        params: Map<String, Value>,
     },
 
     /// Turn information with transcript
@@ -65,6 +73,16 @@ pub enum FluxResponse {
     },
 }
 
+impl FluxResponse {
+    pub(crate) fn sequence_id(&self) -> u32 {
+        match self {
+            FluxResponse::Connected { sequence_id, .. }
+            | FluxResponse::TurnInfo { sequence_id, .. }
+            | FluxResponse::FatalError { sequence_id, .. } => *sequence_id,
+        }
+    }
+}
+
 /// Turn event types
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[non_exhaustive]
@@ -92,6 +110,140 @@ pub struct FluxWord {
     #[allow(missing_docs)]
     pub word: String,
 
-    #[allow(missing_docs)]
+    /// Defaults to `1.0` if Deepgram leaves it out.
+    #[serde(default = "crate::common::stream_response::full_confidence")]
     pub confidence: f64,
 }
+
+/// Keeps track of when each turn of a Flux stream starts and ends, to
+/// measure how long turns last.
+///
+/// A turn starts at the `audio_window_start` of its
+/// [`StartOfTurn`](TurnEvent::StartOfTurn) and ends at the
+/// `audio_window_end` of its [`EndOfTurn`](TurnEvent::EndOfTurn). A
+/// [`TurnResumed`](TurnEvent::TurnResumed) reopens a turn that had ended,
+/// so it is extended until its next end of turn.
+///
+/// ```
+/// use deepgram::common::flux_response::TurnTracker;
+/// # async fn example(mut handle: deepgram::listen::flux::FluxHandle) {
+/// let mut turns = TurnTracker::new();
+/// while let Some(Ok(response)) = handle.receive().await {
+///     turns.push(&response);
+/// }
+/// if let Some(duration) = turns.turn_duration(0) {
+///     println!("The first turn lasted {duration:.2}s");
+/// }
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TurnTracker {
+    turns: BTreeMap<u32, TurnWindow>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TurnWindow {
+    start: f64,
+    end: Option<f64>,
+}
+
+impl TurnTracker {
+    /// Start without any turns.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the turns with a response from a Flux stream.
+    ///
+    /// Responses that are not [`FluxResponse::TurnInfo`] are ignored. A turn
+    /// whose `StartOfTurn` was missed starts with the first event seen for it.
+    pub fn push(&mut self, response: &FluxResponse) {
+        let FluxResponse::TurnInfo {
+            event,
+            turn_index,
+            audio_window_start,
+            audio_window_end,
+            ..
+        } = response
+        else {
+            return;
+        };
+
+        let turn = self.turns.entry(*turn_index).or_insert(TurnWindow {
+            start: *audio_window_start,
+            end: None,
+        });
+        match event {
+            TurnEvent::EndOfTurn => turn.end = Some(*audio_window_end),
+            TurnEvent::TurnResumed => turn.end = None,
+            _ => {}
+        }
+    }
+
+    /// The duration in seconds of audio between the start and the end of
+    /// the turn, or `None` if it has not ended or was never seen.
+    pub fn turn_duration(&self, turn_index: u32) -> Option<f64> {
+        let turn = self.turns.get(&turn_index)?;
+        Some(turn.end? - turn.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FluxResponse, FluxWord, TurnTracker};
+
+    #[test]
+    fn word_without_confidence() {
+        let words: Vec<FluxWord> =
+            serde_json::from_str(r#"[{"word": "hello", "confidence": 0.75}, {"word": "there"}]"#)
+                .unwrap();
+        assert_eq!(words[0].confidence, 0.75);
+        assert_eq!(words[1].word, "there");
+        assert_eq!(words[1].confidence, 1.0);
+    }
+
+    fn turn(event: &str, turn_index: u32, start: f64, end: f64) -> FluxResponse {
+        serde_json::from_str(&format!(
+            r#"{{
+                "type": "TurnInfo",
+                "request_id": "00000000-0000-4000-8000-000000000000",
+                "sequence_id": 1,
+                "event": "{event}",
+                "turn_index": {turn_index},
+                "audio_window_start": {start},
+                "audio_window_end": {end},
+                "transcript": "",
+                "words": [],
+                "end_of_turn_confidence": 0.9
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn turn_lifecycle() {
+        let mut turns = TurnTracker::new();
+        turns.push(&turn("StartOfTurn", 0, 0.5, 0.75));
+        turns.push(&turn("Update", 0, 0.5, 1.0));
+        assert_eq!(turns.turn_duration(0), None);
+
+        turns.push(&turn("EagerEndOfTurn", 0, 0.5, 1.5));
+        assert_eq!(turns.turn_duration(0), None);
+        turns.push(&turn("TurnResumed", 0, 0.5, 1.75));
+        turns.push(&turn("EndOfTurn", 0, 0.5, 2.0));
+        assert_eq!(turns.turn_duration(0), Some(1.5));
+
+        // The turn went on after it seemed to end.
+        turns.push(&turn("TurnResumed", 0, 0.5, 2.25));
+        assert_eq!(turns.turn_duration(0), None);
+        turns.push(&turn("EndOfTurn", 0, 0.5, 3.0));
+        assert_eq!(turns.turn_duration(0), Some(2.5));
+
+        turns.push(&turn("StartOfTurn", 1, 4.0, 4.25));
+        assert_eq!(turns.turn_duration(1), None);
+        turns.push(&turn("EndOfTurn", 1, 4.0, 5.0));
+        assert_eq!(turns.turn_duration(1), Some(1.0));
+        assert_eq!(turns.turn_duration(0), Some(2.5));
+        assert_eq!(turns.turn_duration(2), None);
+    }
+}
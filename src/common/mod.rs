@@ -6,3 +6,6 @@ pub mod batch_response;
 pub mod flux_response;
 pub mod options;
 pub mod stream_response;
+pub mod subtitles;
+#[cfg(feature = "listen")]
+pub mod transcript;
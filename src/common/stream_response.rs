@@ -1,13 +1,19 @@
 //! Stream Response module
 
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use uuid::Uuid;
+
+use super::batch_response::Paragraphs;
 
 /// A single transcribed word.
 ///
 /// See the [Deepgram API Reference][api] for more info.
 ///
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialize", serde(deny_unknown_fields))]
 pub struct Word {
     #[allow(missing_docs)]
     pub word: String,
@@ -18,7 +24,8 @@ pub struct Word {
     #[allow(missing_docs)]
     pub end: f64,
 
-    #[allow(missing_docs)]
+    /// Defaults to `1.0` if Deepgram leaves it out.
+    #[serde(default = "full_confidence")]
     pub confidence: f64,
 
     #[allow(missing_docs)]
@@ -27,16 +34,91 @@ pub struct Word {
     #[allow(missing_docs)]
     pub punctuated_word: Option<String>,
 
-    #[allow(missing_docs)]
+    /// The language the word was spoken in.
+    ///
+    /// [`None`] unless transcribing with
+    /// [`Language::multi`](crate::common::options::Language::multi).
     pub language: Option<String>,
 }
 
+/// The confidence of words whose confidence is missing, so that they still
+/// parse.
+pub(crate) fn full_confidence() -> f64 {
+    1.0
+}
+
+impl Word {
+    /// Return a copy of this word with `offset` seconds added to its start
+    /// and end times.
+    ///
+    /// Useful to move the words of audio streamed in segments onto the
+    /// timeline of the whole recording.
+    pub fn shifted(&self, offset: f64) -> Word {
+        let mut word = self.clone();
+        word.shift_times(offset);
+        word
+    }
+
+    fn shift_times(&mut self, offset: f64) {
+        self.start += offset;
+        self.end += offset;
+    }
+}
+
+/// Split `words` into utterances wherever the silence between the end of a
+/// word and the start of the next is longer than `gap_secs`.
+///
+/// ```
+/// # use deepgram::common::stream_response::{segment_by_gap, Word};
+/// # fn example(words: &[Word]) {
+/// for utterance in segment_by_gap(words, 0.8) {
+///     let text: Vec<_> = utterance.iter().map(|word| word.word.as_str()).collect();
+///     println!("{}", text.join(" "));
+/// }
+/// # }
+/// ```
+pub fn segment_by_gap(words: &[Word], gap_secs: f64) -> Vec<Vec<&Word>> {
+    let mut segments: Vec<Vec<&Word>> = Vec::new();
+    let mut previous_end = None;
+    for word in words {
+        match (segments.last_mut(), previous_end) {
+            (Some(segment), Some(end)) if word.start - end <= gap_secs => segment.push(word),
+            _ => segments.push(vec![word]),
+        }
+        previous_end = Some(word.end);
+    }
+    segments
+}
+
+/// A run of consecutive words spoken by the same speaker.
+///
+/// See [`Alternatives::by_speaker`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SpeakerSegment {
+    /// The speaker, or [`None`] for words without a speaker, such as when the
+    /// [Diarization feature][docs] is not set.
+    ///
+    /// [docs]: https://developers.deepgram.com/docs/diarization
+    pub speaker: Option<i32>,
+
+    /// The words of the segment separated by spaces, punctuated if available.
+    pub text: String,
+
+    /// The start time of the first word, in seconds.
+    pub start: f64,
+
+    /// The end time of the last word, in seconds.
+    pub end: f64,
+}
+
 /// Transcript alternatives.
 ///
 /// See the [Deepgram API Reference][api] for more info.
 ///
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialize", serde(deny_unknown_fields))]
 pub struct Alternatives {
     #[allow(missing_docs)]
     pub transcript: String,
@@ -50,6 +132,86 @@ pub struct Alternatives {
     #[allow(missing_docs)]
     #[serde(default)]
     pub languages: Vec<String>,
+
+    /// [`None`] unless the [Paragraphs feature][docs] is set, or implied by
+    /// the Smart Format feature, and the result is final.
+    ///
+    /// [docs]: https://developers.deepgram.com/docs/paragraphs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paragraphs: Option<Paragraphs>,
+}
+
+impl Alternatives {
+    /// Split the words into runs of consecutive words spoken in the same
+    /// language, each paired with that language.
+    ///
+    /// Words are only tagged with their language when transcribing with
+    /// [`Language::multi`](crate::common::options::Language::multi).
+    /// Untagged words are grouped under [`None`].
+    pub fn words_by_language(&self) -> Vec<(Option<&str>, &[Word])> {
+        self.words
+            .chunk_by(|a, b| a.language == b.language)
+            .map(|run| (run[0].language.as_deref(), run))
+            .collect()
+    }
+
+    /// Group the words into runs of consecutive words spoken by the same
+    /// speaker, in order.
+    ///
+    /// ```
+    /// # use deepgram::common::stream_response::Alternatives;
+    /// # fn example(alternatives: &Alternatives) {
+    /// for segment in alternatives.by_speaker() {
+    ///     match segment.speaker {
+    ///         Some(speaker) => println!("Speaker {speaker}: {}", segment.text),
+    ///         None => println!("Unknown speaker: {}", segment.text),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn by_speaker(&self) -> Vec<SpeakerSegment> {
+        self.words
+            .chunk_by(|a, b| a.speaker == b.speaker)
+            .map(|run| SpeakerSegment {
+                speaker: run[0].speaker,
+                text: run
+                    .iter()
+                    .map(|word| word.punctuated_word.as_deref().unwrap_or(&word.word))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                start: run[0].start,
+                end: run[run.len() - 1].end,
+            })
+            .collect()
+    }
+
+    /// The words transcribed with a confidence below `threshold`, in order.
+    ///
+    /// Useful to flag the uncertain parts of a transcript for review.
+    pub fn low_confidence_words(&self, threshold: f64) -> Vec<&Word> {
+        self.words
+            .iter()
+            .filter(|word| word.confidence < threshold)
+            .collect()
+    }
+
+    /// The mean confidence of the words, or [`None`] if there are none.
+    pub fn average_confidence(&self) -> Option<f64> {
+        if self.words.is_empty() {
+            return None;
+        }
+        let total: f64 = self.words.iter().map(|word| word.confidence).sum();
+        Some(total / self.words.len() as f64)
+    }
+
+    /// Add `offset` seconds to the start and end times of every word.
+    ///
+    /// See [`Word::shifted`].
+    pub fn shift_times(&mut self, offset: f64) {
+        for word in &mut self.words {
+            word.shift_times(offset);
+        }
+    }
 }
 
 /// Transcription results for a single audio channel.
@@ -59,14 +221,28 @@ pub struct Alternatives {
 ///
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
 /// [docs]: https://developers.deepgram.com/documentation/features/multichannel/
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialize", serde(deny_unknown_fields))]
 pub struct Channel {
     #[allow(missing_docs)]
     pub alternatives: Vec<Alternatives>,
 }
 
+impl Channel {
+    /// Add `offset` seconds to the start and end times of every word of
+    /// every alternative.
+    ///
+    /// See [`Word::shifted`].
+    pub fn shift_times(&mut self, offset: f64) {
+        for alternative in &mut self.alternatives {
+            alternative.shift_times(offset);
+        }
+    }
+}
+
 /// Modle info
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialize", serde(deny_unknown_fields))]
 pub struct ModelInfo {
     #[allow(missing_docs)]
     pub name: String,
@@ -83,7 +259,8 @@ pub struct ModelInfo {
 /// See the [Deepgram API Reference][api] for more info.
 ///
 /// [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialize", serde(deny_unknown_fields))]
 pub struct Metadata {
     #[allow(missing_docs)]
     pub request_id: String,
@@ -95,8 +272,26 @@ pub struct Metadata {
     pub model_uuid: String,
 }
 
+/// Which channel of a multichannel stream a transcript is for, parsed from
+/// its `channel_index`.
+///
+/// See [`StreamResponse::channel_index`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ChannelIndex {
+    /// The zero-based index of the channel.
+    pub channel: u32,
+
+    /// How many channels the audio has.
+    pub channels: u32,
+}
+
 /// Possible websocket message types
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Messages are parsed according to their `"type"` field, so a message of a
+/// known type that doesn't match its variant is an error rather than a
+/// [`StreamResponse::Unknown`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
 #[non_exhaustive]
 pub enum StreamResponse {
@@ -130,7 +325,46 @@ pub enum StreamResponse {
         #[allow(missing_docs)]
         channel_index: Vec<i32>,
     },
+    /// The metadata of the request, sent with `"type": "Metadata"` once
+    /// Deepgram has processed all the audio after a `CloseStream`.
+    MetadataResponse {
+        #[allow(missing_docs)]
+        #[serde(rename = "type")]
+        type_field: String,
+
+        /// No longer used by Deepgram.
+        #[serde(default)]
+        transaction_key: String,
+
+        /// The Deepgram request ID.
+        request_id: String,
+
+        /// The SHA-256 hash of the audio received.
+        #[serde(default)]
+        sha256: String,
+
+        /// When the request was received, as an ISO 8601 timestamp.
+        created: String,
+
+        /// The duration of the audio received, in seconds.
+        duration: f64,
+
+        /// The number of audio channels.
+        channels: u32,
+
+        /// The UUIDs of the models used.
+        #[serde(default)]
+        models: Vec<String>,
+
+        /// Information about each of the models used, by UUID.
+        #[serde(default)]
+        model_info: HashMap<String, ModelInfo>,
+    },
     #[allow(missing_docs)]
+    #[deprecated(
+        since = "0.8.0",
+        note = "Metadata messages are parsed as `StreamResponse::MetadataResponse`"
+    )]
     TerminalResponse {
         #[allow(missing_docs)]
         request_id: String,
@@ -168,4 +402,900 @@ pub enum StreamResponse {
         #[allow(missing_docs)]
         last_word_end: f64,
     },
+    /// A non-fatal problem with the request, sent with `"type": "Warning"`.
+    ///
+    /// The connection stays open and transcription continues.
+    #[serde(with = "warning")]
+    Warning {
+        /// A machine-readable code for the problem.
+        code: String,
+
+        /// A description of the problem.
+        description: String,
+    },
+    /// Emitted by the client rather than Deepgram once a dropped live connection
+    /// has been re-established.
+    ///
+    /// See [`WebsocketBuilder::reconnect`](crate::listen::websocket::WebsocketBuilder::reconnect).
+    ///
+    /// Unlike the other variants, it cannot be serialized.
+    #[serde(skip)]
+    Reconnected {
+        /// The attempt on which the connection was re-established, starting at 1.
+        attempt: u32,
+
+        /// The Deepgram request ID of the new connection.
+        request_id: Uuid,
+    },
+    /// Emitted by the client rather than Deepgram when nothing has flowed
+    /// on a live connection for a while.
+    ///
+    /// See [`WebsocketBuilder::idle_timeout`](crate::listen::websocket::WebsocketBuilder::idle_timeout).
+    ///
+    /// Like [`StreamResponse::Reconnected`], it cannot be serialized.
+    #[serde(skip)]
+    Idle {
+        /// How long no audio has been sent and no message received.
+        duration: std::time::Duration,
+    },
+    /// A message this version of the SDK does not recognize, as raw JSON.
+    ///
+    /// New message types sent by Deepgram land here until the SDK gains a
+    /// typed variant for them, so they can still be handled by inspecting
+    /// their `"type"` field.
+    ///
+    /// With the `strict-deserialize` feature, unrecognized messages are
+    /// errors instead.
+    Unknown(serde_json::Value),
+}
+
+impl<'de> Deserialize<'de> for StreamResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let response = match value.get("type").and_then(serde_json::Value::as_str) {
+            Some("Results" | "Metadata" | "SpeechStarted" | "UtteranceEnd") => {
+                tagged::Tagged::deserialize(value).map(StreamResponse::from)
+            }
+            Some("Warning") => warning::deserialize(value)
+                .map(|(code, description)| StreamResponse::Warning { code, description }),
+            // Serialized by older versions of the SDK, without a tag.
+            None => match tagged::Terminal::deserialize(&value) {
+                Ok(terminal) => Ok(terminal.into()),
+                Err(_) => return unknown(value),
+            },
+            Some(_) => return unknown(value),
+        };
+        response.map_err(D::Error::custom)
+    }
+}
+
+/// A message of a type this version of the SDK does not recognize.
+fn unknown<E: serde::de::Error>(value: serde_json::Value) -> Result<StreamResponse, E> {
+    #[cfg(not(feature = "strict-deserialize"))]
+    return Ok(StreamResponse::Unknown(value));
+    #[cfg(feature = "strict-deserialize")]
+    Err(E::custom(format_args!(
+        "unrecognized message type {}",
+        value["type"]
+    )))
+}
+
+impl StreamResponse {
+    /// Whether this is the request metadata that ends a stream.
+    #[allow(deprecated)]
+    pub(crate) fn is_metadata(&self) -> bool {
+        matches!(
+            self,
+            StreamResponse::MetadataResponse { .. } | StreamResponse::TerminalResponse { .. }
+        )
+    }
+
+    /// Whether this is the transcript Deepgram sends once it has processed
+    /// all the audio received before a Finalize message.
+    ///
+    /// See [`WebsocketHandle::finalize`](crate::listen::websocket::WebsocketHandle::finalize).
+    pub fn is_finalize_ack(&self) -> bool {
+        matches!(
+            self,
+            StreamResponse::TranscriptResponse {
+                from_finalize: true,
+                ..
+            }
+        )
+    }
+
+    /// The channel a transcript is for, or `None` if this is not a transcript.
+    ///
+    /// With [`multichannel`](crate::common::options::OptionsBuilder::multichannel)
+    /// set, Deepgram transcribes each channel separately and sends a
+    /// transcript per channel.
+    pub fn channel_index(&self) -> Option<ChannelIndex> {
+        let StreamResponse::TranscriptResponse { channel_index, .. } = self else {
+            return None;
+        };
+        match channel_index[..] {
+            [channel, channels] => Some(ChannelIndex {
+                channel: channel.try_into().ok()?,
+                channels: channels.try_into().ok()?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The transcribed channel, if this is a transcript for channel `index`.
+    ///
+    /// Returns `None` for transcripts of other channels and for every other
+    /// kind of response.
+    pub fn channel_for(&self, index: u32) -> Option<&Channel> {
+        match self {
+            StreamResponse::TranscriptResponse { channel, .. }
+                if self.channel_index()?.channel == index =>
+            {
+                Some(channel)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The known message types, tagged by their `"type"` field.
+mod tagged {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use super::{Channel, Metadata, ModelInfo, StreamResponse};
+
+    #[derive(Deserialize)]
+    #[serde(tag = "type")]
+    #[cfg_attr(feature = "strict-deserialize", serde(deny_unknown_fields))]
+    pub(super) enum Tagged {
+        Results {
+            start: f64,
+            duration: f64,
+            is_final: bool,
+            speech_final: bool,
+            from_finalize: bool,
+            channel: Channel,
+            metadata: Metadata,
+            channel_index: Vec<i32>,
+        },
+        Metadata {
+            #[serde(default)]
+            transaction_key: String,
+            request_id: String,
+            #[serde(default)]
+            sha256: String,
+            created: String,
+            duration: f64,
+            channels: u32,
+            #[serde(default)]
+            models: Vec<String>,
+            #[serde(default)]
+            model_info: HashMap<String, ModelInfo>,
+        },
+        SpeechStarted {
+            channel: Vec<u8>,
+            timestamp: f64,
+        },
+        UtteranceEnd {
+            channel: Vec<u8>,
+            last_word_end: f64,
+        },
+    }
+
+    /// [`StreamResponse::TerminalResponse`], which has no tag.
+    #[derive(Deserialize)]
+    #[cfg_attr(feature = "strict-deserialize", serde(deny_unknown_fields))]
+    pub(super) struct Terminal {
+        request_id: String,
+        created: String,
+        duration: f64,
+        channels: u32,
+    }
+
+    impl From<Terminal> for StreamResponse {
+        #[allow(deprecated)]
+        fn from(terminal: Terminal) -> Self {
+            StreamResponse::TerminalResponse {
+                request_id: terminal.request_id,
+                created: terminal.created,
+                duration: terminal.duration,
+                channels: terminal.channels,
+            }
+        }
+    }
+
+    impl From<Tagged> for StreamResponse {
+        fn from(tagged: Tagged) -> Self {
+            match tagged {
+                Tagged::Results {
+                    start,
+                    duration,
+                    is_final,
+                    speech_final,
+                    from_finalize,
+                    channel,
+                    metadata,
+                    channel_index,
+                } => StreamResponse::TranscriptResponse {
+                    type_field: "Results".to_string(),
+                    start,
+                    duration,
+                    is_final,
+                    speech_final,
+                    from_finalize,
+                    channel,
+                    metadata,
+                    channel_index,
+                },
+                Tagged::Metadata {
+                    transaction_key,
+                    request_id,
+                    sha256,
+                    created,
+                    duration,
+                    channels,
+                    models,
+                    model_info,
+                } => StreamResponse::MetadataResponse {
+                    type_field: "Metadata".to_string(),
+                    transaction_key,
+                    request_id,
+                    sha256,
+                    created,
+                    duration,
+                    channels,
+                    models,
+                    model_info,
+                },
+                Tagged::SpeechStarted { channel, timestamp } => {
+                    StreamResponse::SpeechStartedResponse {
+                        type_field: "SpeechStarted".to_string(),
+                        channel,
+                        timestamp,
+                    }
+                }
+                Tagged::UtteranceEnd {
+                    channel,
+                    last_word_end,
+                } => StreamResponse::UtteranceEndResponse {
+                    type_field: "UtteranceEnd".to_string(),
+                    channel,
+                    last_word_end,
+                },
+            }
+        }
+    }
+}
+
+/// (De)serializes [`StreamResponse::Warning`] with its `"type"` tag, which
+/// an untagged variant would otherwise neither check nor write.
+mod warning {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    #[cfg_attr(feature = "strict-deserialize", serde(deny_unknown_fields))]
+    enum Tagged<S> {
+        Warning { code: S, description: S },
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        code: &str,
+        description: &str,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Tagged::Warning { code, description }.serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<(String, String), D::Error> {
+        let Tagged::Warning { code, description } = Tagged::deserialize(deserializer)?;
+        Ok((code, description))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        segment_by_gap, Alternatives, Channel, ChannelIndex, Metadata, ModelInfo, SpeakerSegment,
+        StreamResponse, Word,
+    };
+
+    fn word(word: &str, start: f64, end: f64) -> Word {
+        Word {
+            word: word.to_string(),
+            start,
+            end,
+            confidence: 0.9,
+            speaker: None,
+            punctuated_word: None,
+            language: None,
+        }
+    }
+
+    #[test]
+    fn segments_split_at_gaps() {
+        let words = [
+            word("hello", 0.0, 0.4),
+            word("there", 0.5, 0.9),
+            word("how", 2.0, 2.25),
+            word("are", 2.25, 2.5),
+            // Exactly the gap: still the same utterance.
+            word("you", 3.0, 3.25),
+        ];
+
+        let segments: Vec<Vec<&str>> = segment_by_gap(&words, 0.5)
+            .into_iter()
+            .map(|segment| segment.iter().map(|word| word.word.as_str()).collect())
+            .collect();
+        assert_eq!(
+            segments,
+            [vec!["hello", "there"], vec!["how", "are", "you"]]
+        );
+
+        assert!(segment_by_gap(&[], 0.5).is_empty());
+        assert_eq!(segment_by_gap(&words, 10.0).len(), 1);
+    }
+
+    #[test]
+    fn shifted_word() {
+        let original = word("hello", 0.25, 0.5);
+        let shifted = original.shifted(10.0);
+
+        assert_eq!((shifted.start, shifted.end), (10.25, 10.5));
+        assert_eq!(shifted.word, "hello");
+        assert_eq!((original.start, original.end), (0.25, 0.5));
+    }
+
+    #[test]
+    fn shift_channel_times() {
+        let mut channel: Channel = serde_json::from_str(
+            r#"{"alternatives": [
+                {"transcript": "hello world", "confidence": 0.9, "words": [
+                    {"word": "hello", "start": 0.0, "end": 0.5, "confidence": 0.9},
+                    {"word": "world", "start": 0.5, "end": 1.0, "confidence": 0.9}
+                ]},
+                {"transcript": "", "confidence": 0.1, "words": []}
+            ]}"#,
+        )
+        .unwrap();
+        channel.shift_times(2.0);
+
+        let times: Vec<_> = channel.alternatives[0]
+            .words
+            .iter()
+            .map(|word| (word.start, word.end))
+            .collect();
+        assert_eq!(times, [(2.0, 2.5), (2.5, 3.0)]);
+        assert!(channel.alternatives[1].words.is_empty());
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let mut words = vec![word("hello", 0.0, 0.5), word("world", 0.5, 1.0)];
+        words[0].speaker = Some(1);
+        words[0].punctuated_word = Some("Hello".to_string());
+        words[1].language = Some("en".to_string());
+        let transcript = StreamResponse::TranscriptResponse {
+            type_field: "Results".to_string(),
+            start: 0.0,
+            duration: 1.0,
+            is_final: true,
+            speech_final: false,
+            from_finalize: true,
+            channel: Channel {
+                alternatives: vec![Alternatives {
+                    transcript: "hello world".to_string(),
+                    words,
+                    confidence: 0.9,
+                    languages: vec!["en".to_string()],
+                    paragraphs: None,
+                }],
+            },
+            metadata: Metadata {
+                request_id: "00000000-0000-4000-8000-000000000000".to_string(),
+                model_info: ModelInfo {
+                    name: "general".to_string(),
+                    version: "1".to_string(),
+                    arch: "nova-3".to_string(),
+                },
+                model_uuid: "00000000-0000-4000-8000-000000000001".to_string(),
+            },
+            channel_index: vec![0, 1],
+        };
+
+        #[cfg_attr(feature = "strict-deserialize", allow(unused_mut))]
+        #[allow(deprecated)]
+        let mut responses = vec![
+            transcript,
+            StreamResponse::MetadataResponse {
+                type_field: "Metadata".to_string(),
+                transaction_key: "deprecated".to_string(),
+                request_id: "00000000-0000-4000-8000-000000000000".to_string(),
+                sha256: "6d3c8b7a".to_string(),
+                created: "2025-01-01T00:00:00.000Z".to_string(),
+                duration: 1.0,
+                channels: 1,
+                models: vec!["00000000-0000-4000-8000-000000000001".to_string()],
+                model_info: [(
+                    "00000000-0000-4000-8000-000000000001".to_string(),
+                    ModelInfo {
+                        name: "general".to_string(),
+                        version: "1".to_string(),
+                        arch: "nova-3".to_string(),
+                    },
+                )]
+                .into(),
+            },
+            StreamResponse::TerminalResponse {
+                request_id: "00000000-0000-4000-8000-000000000000".to_string(),
+                created: "2025-01-01T00:00:00.000Z".to_string(),
+                duration: 1.0,
+                channels: 1,
+            },
+            StreamResponse::SpeechStartedResponse {
+                type_field: "SpeechStarted".to_string(),
+                channel: vec![0, 1],
+                timestamp: 0.25,
+            },
+            StreamResponse::UtteranceEndResponse {
+                type_field: "UtteranceEnd".to_string(),
+                channel: vec![0, 1],
+                last_word_end: 1.0,
+            },
+            StreamResponse::Warning {
+                code: "W0001".to_string(),
+                description: "Audio is too quiet".to_string(),
+            },
+        ];
+        #[cfg(not(feature = "strict-deserialize"))]
+        responses.push(StreamResponse::Unknown(
+            serde_json::json!({"type": "SomethingNew", "value": 42}),
+        ));
+
+        for response in responses {
+            let json = serde_json::to_string(&response).unwrap();
+            let parsed: StreamResponse = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, response, "{json}");
+        }
+
+        // Only emitted by the client, so never persisted.
+        let reconnected = StreamResponse::Reconnected {
+            attempt: 1,
+            request_id: uuid::Uuid::nil(),
+        };
+        assert!(serde_json::to_string(&reconnected).is_err());
+        let idle = StreamResponse::Idle {
+            duration: std::time::Duration::from_secs(1),
+        };
+        assert!(serde_json::to_string(&idle).is_err());
+    }
+
+    #[cfg(not(feature = "strict-deserialize"))]
+    #[test]
+    fn unknown_message_type() {
+        let response: StreamResponse =
+            serde_json::from_str(r#"{"type": "SomethingNew", "value": 42}"#).unwrap();
+
+        let StreamResponse::Unknown(value) = response else {
+            panic!("expected an unknown response, got {response:?}");
+        };
+        assert_eq!(value["type"], "SomethingNew");
+        assert_eq!(value["value"], 42);
+    }
+
+    #[test]
+    fn malformed_known_message_type() {
+        let err = serde_json::from_str::<StreamResponse>(r#"{"type": "Results", "start": "oops"}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid type"), "{err}");
+
+        assert!(serde_json::from_str::<StreamResponse>(r#"{"type": "Metadata"}"#).is_err());
+        assert!(serde_json::from_str::<StreamResponse>(r#"{"type": "Warning"}"#).is_err());
+    }
+
+    #[test]
+    fn metadata() {
+        let response: StreamResponse = serde_json::from_str(
+            r#"{
+                "type": "Metadata",
+                "transaction_key": "deprecated",
+                "request_id": "a4fbbf0c-1a3e-4b6a-a41e-7e2e3a0c6b2d",
+                "sha256": "3c5c8e8f1f2d2f2f0a6b0c7f4f8e9d3b1c2a5e6f7a8b9c0d1e2f3a4b5c6d7e8f",
+                "created": "2025-03-04T15:22:41.096Z",
+                "duration": 12.48,
+                "channels": 1,
+                "models": ["1abfe86b-e047-4eed-858a-35e5625b41ee"],
+                "model_info": {
+                    "1abfe86b-e047-4eed-858a-35e5625b41ee": {
+                        "name": "general-nova-3",
+                        "version": "2024-12-20.0",
+                        "arch": "nova-3"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let StreamResponse::MetadataResponse {
+            request_id,
+            duration,
+            channels,
+            models,
+            model_info,
+            ..
+        } = &response
+        else {
+            panic!("expected a metadata response, got {response:?}");
+        };
+        assert_eq!(request_id, "a4fbbf0c-1a3e-4b6a-a41e-7e2e3a0c6b2d");
+        assert_eq!((*duration, *channels), (12.48, 1));
+        assert_eq!(models[0], "1abfe86b-e047-4eed-858a-35e5625b41ee");
+        assert_eq!(model_info[&models[0]].arch, "nova-3");
+        assert!(response.is_metadata());
+    }
+
+    #[test]
+    fn warning() {
+        let response: StreamResponse = serde_json::from_str(
+            r#"{
+                "type": "Warning",
+                "code": "UNSUPPORTED_PARAMETER",
+                "description": "The 'foo' parameter is not supported and was ignored."
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            StreamResponse::Warning {
+                code: "UNSUPPORTED_PARAMETER".to_string(),
+                description: "The 'foo' parameter is not supported and was ignored.".to_string(),
+            }
+        );
+
+        // Other messages with the same fields are not warnings.
+        let response: Result<StreamResponse, _> = serde_json::from_str(
+            r#"{"type": "Error", "code": "INTERNAL", "description": "Something broke."}"#,
+        );
+        assert!(!matches!(response, Ok(StreamResponse::Warning { .. })));
+    }
+
+    #[test]
+    fn channel_for() {
+        let transcript = |channel: u32| -> StreamResponse {
+            serde_json::from_str(&format!(
+                r#"{{
+                    "type": "Results",
+                    "start": 0.0,
+                    "duration": 1.5,
+                    "is_final": true,
+                    "speech_final": true,
+                    "from_finalize": false,
+                    "channel": {{"alternatives": [{{
+                        "transcript": "channel {channel}",
+                        "confidence": 0.9,
+                        "words": []
+                    }}]}},
+                    "metadata": {{
+                        "request_id": "00000000-0000-4000-8000-000000000000",
+                        "model_info": {{"name": "general", "version": "1", "arch": "nova-3"}},
+                        "model_uuid": "00000000-0000-4000-8000-000000000001"
+                    }},
+                    "channel_index": [{channel}, 2]
+                }}"#
+            ))
+            .unwrap()
+        };
+        let responses = [transcript(1), transcript(0)];
+
+        for index in 0..2 {
+            let channels: Vec<_> = responses
+                .iter()
+                .filter_map(|response| response.channel_for(index))
+                .collect();
+            assert_eq!(channels.len(), 1);
+            assert_eq!(
+                channels[0].alternatives[0].transcript,
+                format!("channel {index}")
+            );
+        }
+        assert_eq!(
+            responses[0].channel_index(),
+            Some(ChannelIndex {
+                channel: 1,
+                channels: 2
+            })
+        );
+        assert!(responses
+            .iter()
+            .all(|response| response.channel_for(2).is_none()));
+
+        let warning = StreamResponse::Warning {
+            code: "UNSUPPORTED_PARAMETER".to_string(),
+            description: "ignored".to_string(),
+        };
+        assert_eq!(warning.channel_index(), None);
+        assert_eq!(warning.channel_for(0), None);
+    }
+
+    #[test]
+    fn word_without_confidence() {
+        let word: Word =
+            serde_json::from_str(r#"{"word": "hello", "start": 0.0, "end": 0.5}"#).unwrap();
+        assert_eq!(word.confidence, 1.0);
+        assert_eq!(word.word, "hello");
+    }
+
+    #[test]
+    fn word_confidence() {
+        let mut words = vec![
+            word("hello", 0.0, 0.5),
+            word("wurld", 0.5, 1.0),
+            word("how", 1.0, 1.5),
+            word("r", 1.5, 2.0),
+        ];
+        for (word, confidence) in words.iter_mut().zip([1.0, 0.25, 0.75, 0.5]) {
+            word.confidence = confidence;
+        }
+        let mut alternatives = Alternatives {
+            transcript: "hello wurld how r".to_string(),
+            words,
+            confidence: 0.8,
+            languages: Vec::new(),
+            paragraphs: None,
+        };
+
+        let flagged: Vec<&str> = alternatives
+            .low_confidence_words(0.75)
+            .iter()
+            .map(|word| word.word.as_str())
+            .collect();
+        assert_eq!(flagged, ["wurld", "r"]);
+        assert!(alternatives.low_confidence_words(0.25).is_empty());
+        assert_eq!(alternatives.average_confidence(), Some(0.625));
+
+        alternatives.words.clear();
+        assert_eq!(alternatives.average_confidence(), None);
+    }
+
+    #[test]
+    fn finalize_ack() {
+        let response: StreamResponse = serde_json::from_str(
+            r#"{
+                "type": "Results",
+                "start": 0.0,
+                "duration": 1.5,
+                "is_final": true,
+                "speech_final": false,
+                "from_finalize": true,
+                "channel": {"alternatives": [{
+                    "transcript": "hello",
+                    "confidence": 0.9,
+                    "words": []
+                }]},
+                "metadata": {
+                    "request_id": "00000000-0000-4000-8000-000000000000",
+                    "model_info": {"name": "general", "version": "1", "arch": "nova-3"},
+                    "model_uuid": "00000000-0000-4000-8000-000000000001"
+                },
+                "channel_index": [0, 1]
+            }"#,
+        )
+        .unwrap();
+        assert!(response.is_finalize_ack());
+
+        let StreamResponse::TranscriptResponse {
+            channel, metadata, ..
+        } = response
+        else {
+            unreachable!();
+        };
+        let regular = StreamResponse::TranscriptResponse {
+            type_field: "Results".to_string(),
+            start: 0.0,
+            duration: 1.5,
+            is_final: true,
+            speech_final: false,
+            from_finalize: false,
+            channel,
+            metadata,
+            channel_index: vec![0, 1],
+        };
+        assert!(!regular.is_finalize_ack());
+
+        let utterance_end = StreamResponse::UtteranceEndResponse {
+            type_field: "UtteranceEnd".to_string(),
+            channel: vec![0, 1],
+            last_word_end: 1.5,
+        };
+        assert!(!utterance_end.is_finalize_ack());
+    }
+
+    #[test]
+    fn smart_format_paragraphs() {
+        let response: StreamResponse = serde_json::from_str(
+            r#"{
+                "type": "Results",
+                "start": 0.0,
+                "duration": 3.0,
+                "is_final": true,
+                "speech_final": true,
+                "from_finalize": false,
+                "channel": {"alternatives": [{
+                    "transcript": "It costs $20. See you at 3 PM.",
+                    "confidence": 0.98,
+                    "words": [],
+                    "paragraphs": {
+                        "transcript": "\nIt costs $20. See you at 3 PM.",
+                        "paragraphs": [{
+                            "sentences": [
+                                {"text": "It costs $20.", "start": 0.0, "end": 1.25},
+                                {"text": "See you at 3 PM.", "start": 1.5, "end": 3.0}
+                            ],
+                            "num_words": 8,
+                            "start": 0.0,
+                            "end": 3.0
+                        }]
+                    }
+                }]},
+                "metadata": {
+                    "request_id": "00000000-0000-4000-8000-000000000000",
+                    "model_info": {"name": "general", "version": "1", "arch": "nova-3"},
+                    "model_uuid": "00000000-0000-4000-8000-000000000001"
+                },
+                "channel_index": [0, 1]
+            }"#,
+        )
+        .unwrap();
+
+        let StreamResponse::TranscriptResponse { channel, .. } = response else {
+            panic!("expected a transcript, got {response:?}");
+        };
+        let paragraphs = channel.alternatives[0].paragraphs.as_ref().unwrap();
+        assert_eq!(paragraphs.paragraphs.len(), 1);
+        let sentences = &paragraphs.paragraphs[0].sentences;
+        assert_eq!(sentences[0].text, "It costs $20.");
+        assert_eq!((sentences[1].start, sentences[1].end), (1.5, 3.0));
+
+        // Interim results come without paragraphs.
+        assert_eq!(
+            serde_json::from_str::<Alternatives>(
+                r#"{"transcript": "it costs", "confidence": 0.9, "words": []}"#
+            )
+            .unwrap()
+            .paragraphs,
+            None
+        );
+    }
+
+    #[test]
+    fn words_by_language() {
+        let alternatives: Alternatives = serde_json::from_str(
+            r#"{
+                "transcript": "hello my friend como estas today",
+                "confidence": 0.9,
+                "languages": ["en", "es"],
+                "words": [
+                    {"word": "hello", "start": 0.0, "end": 0.5, "confidence": 0.9, "language": "en"},
+                    {"word": "my", "start": 0.5, "end": 0.7, "confidence": 0.9, "language": "en"},
+                    {"word": "friend", "start": 0.7, "end": 1.0, "confidence": 0.9, "language": "en"},
+                    {"word": "como", "start": 1.0, "end": 1.3, "confidence": 0.9, "language": "es"},
+                    {"word": "estas", "start": 1.3, "end": 1.6, "confidence": 0.9, "language": "es"},
+                    {"word": "today", "start": 1.6, "end": 2.0, "confidence": 0.9, "language": "en"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(alternatives.languages, ["en", "es"]);
+
+        let runs: Vec<_> = alternatives
+            .words_by_language()
+            .into_iter()
+            .map(|(language, words)| {
+                let words: Vec<_> = words.iter().map(|word| word.word.as_str()).collect();
+                (language, words.join(" "))
+            })
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                (Some("en"), "hello my friend".to_string()),
+                (Some("es"), "como estas".to_string()),
+                (Some("en"), "today".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn by_speaker() {
+        let mut words = vec![
+            word("hi", 0.0, 0.25),
+            word("there", 0.25, 0.5),
+            word("hello", 0.75, 1.0),
+            word("again", 1.5, 2.0),
+            word("uh", 2.0, 2.25),
+        ];
+        for (word, speaker) in words
+            .iter_mut()
+            .zip([Some(0), Some(0), Some(1), Some(0), None])
+        {
+            word.speaker = speaker;
+        }
+        words[1].punctuated_word = Some("there.".to_string());
+        let alternatives = Alternatives {
+            transcript: "hi there hello again uh".to_string(),
+            words,
+            confidence: 0.9,
+            languages: Vec::new(),
+            paragraphs: None,
+        };
+
+        let segment = |speaker, text: &str, start, end| SpeakerSegment {
+            speaker,
+            text: text.to_string(),
+            start,
+            end,
+        };
+        assert_eq!(
+            alternatives.by_speaker(),
+            [
+                segment(Some(0), "hi there.", 0.0, 0.5),
+                segment(Some(1), "hello", 0.75, 1.0),
+                segment(Some(0), "again", 1.5, 2.0),
+                segment(None, "uh", 2.0, 2.25),
+            ]
+        );
+    }
+
+    #[cfg(feature = "strict-deserialize")]
+    #[test]
+    fn strict_deserialize_rejects_unknown_fields() {
+        let word = r#"{"word": "hello", "start": 0.0, "end": 0.5, "confidence": 0.9}"#;
+        let results = |word: &str, extra: &str| {
+            format!(
+                r#"{{
+                    "type": "Results",
+                    "start": 0.0,
+                    "duration": 1.5,
+                    "is_final": true,
+                    "speech_final": true,
+                    "from_finalize": false,
+                    "channel": {{"alternatives": [{{"transcript": "hello", "words": [{word}], "confidence": 0.9}}]}},
+                    "metadata": {{
+                        "request_id": "00000000-0000-4000-8000-000000000000",
+                        "model_info": {{"name": "general", "version": "1", "arch": "nova-3"}},
+                        "model_uuid": "00000000-0000-4000-8000-000000000001"
+                    }},
+                    {extra}
+                    "channel_index": [0, 1]
+                }}"#
+            )
+        };
+        assert!(serde_json::from_str::<StreamResponse>(&results(word, "")).is_ok());
+
+        // Nested inside a word.
+        let new_word =
+            r#"{"word": "hello", "start": 0.0, "end": 0.5, "confidence": 0.9, "new_field": 1}"#;
+        assert!(serde_json::from_str::<Word>(new_word).is_err());
+        assert!(serde_json::from_str::<StreamResponse>(&results(new_word, "")).is_err());
+
+        // At the top level.
+        let response = results(word, r#""new_field": 1,"#);
+        assert!(serde_json::from_str::<StreamResponse>(&response).is_err());
+
+        let terminal = r#""request_id": "00000000-0000-4000-8000-000000000000",
+            "created": "2025-01-01T00:00:00.000Z",
+            "duration": 1.0,
+            "channels": 1"#;
+        assert!(serde_json::from_str::<StreamResponse>(&format!("{{{terminal}}}")).is_ok());
+        assert!(serde_json::from_str::<StreamResponse>(&format!(
+            r#"{{{terminal}, "new_field": 1}}"#
+        ))
+        .is_err());
+
+        assert!(serde_json::from_str::<StreamResponse>(r#"{"type": "SomethingNew"}"#).is_err());
+    }
 }
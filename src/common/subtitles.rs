@@ -0,0 +1,223 @@
+//! Export word timings as SRT or WebVTT subtitles.
+//!
+//! Words are grouped into caption cues of bounded length and duration.
+//! The cues themselves can be serialized, for instance to JSON.
+//!
+//! ```
+//! use deepgram::common::{batch_response::Response, subtitles::{self, SubtitleOptions}};
+//!
+//! # fn example(response: &Response) {
+//! let words = &response.results.channels[0].alternatives[0].words;
+//! let srt = subtitles::to_srt(words, SubtitleOptions::default());
+//! # }
+//! ```
+
+use std::{fmt::Write, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use super::batch_response::Word;
+
+/// Controls how words are grouped into cues.
+///
+/// Used with [`to_srt`], [`to_vtt`] and [`cues`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubtitleOptions {
+    max_chars: usize,
+    max_duration: Duration,
+}
+
+impl SubtitleOptions {
+    /// Set the longest text of a cue, in characters. Defaults to 42.
+    ///
+    /// A single word longer than this still gets a cue of its own.
+    pub fn max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    /// Set the longest time a cue stays on screen. Defaults to 7 seconds.
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = max_duration;
+        self
+    }
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            max_chars: 42,
+            max_duration: Duration::from_secs(7),
+        }
+    }
+}
+
+/// A caption shown from `start` to `end`, in seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Cue {
+    #[allow(missing_docs)]
+    pub start: f64,
+
+    #[allow(missing_docs)]
+    pub end: f64,
+
+    #[allow(missing_docs)]
+    pub text: String,
+}
+
+/// Group `words` into cues.
+///
+/// The punctuated form of each word is used when available. Words without
+/// usable timings, such as a negative or non-finite time or an end before
+/// the start, are skipped.
+pub fn cues(words: &[Word], opts: SubtitleOptions) -> Vec<Cue> {
+    let max_duration = opts.max_duration.as_secs_f64();
+    let mut cues: Vec<Cue> = Vec::new();
+    let mut current: Option<Cue> = None;
+
+    for word in words {
+        let timed = word.start.is_finite() && word.end.is_finite();
+        if !timed || word.start < 0.0 || word.end < word.start {
+            continue;
+        }
+        let text = word.punctuated_word.as_deref().unwrap_or(&word.word);
+
+        if let Some(cue) = &mut current {
+            let fits = cue.text.chars().count() + 1 + text.chars().count() <= opts.max_chars
+                && word.end - cue.start <= max_duration;
+            if fits {
+                cue.text.push(' ');
+                cue.text.push_str(text);
+                cue.end = word.end;
+                continue;
+            }
+            cues.extend(current.take());
+        }
+        current = Some(Cue {
+            start: word.start,
+            end: word.end,
+            text: text.to_string(),
+        });
+    }
+    cues.extend(current);
+    cues
+}
+
+/// Format `words` as a SubRip (`.srt`) subtitle file.
+pub fn to_srt(words: &[Word], opts: SubtitleOptions) -> String {
+    let mut srt = String::new();
+    for (index, cue) in cues(words, opts).iter().enumerate() {
+        let _ = write!(
+            srt,
+            "{}\n{} --> {}\n{}\n\n",
+            index + 1,
+            timestamp(cue.start, ','),
+            timestamp(cue.end, ','),
+            cue.text
+        );
+    }
+    srt
+}
+
+/// Format `words` as a WebVTT (`.vtt`) subtitle file.
+pub fn to_vtt(words: &[Word], opts: SubtitleOptions) -> String {
+    let mut vtt = String::from("WEBVTT\n\n");
+    for cue in cues(words, opts) {
+        let _ = write!(
+            vtt,
+            "{} --> {}\n{}\n\n",
+            timestamp(cue.start, '.'),
+            timestamp(cue.end, '.'),
+            cue.text
+        );
+    }
+    vtt
+}
+
+/// Format `seconds` as `HH:MM:SS` followed by `separator` and milliseconds.
+fn timestamp(seconds: f64, separator: char) -> String {
+    let millis = (seconds * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{cues, timestamp, to_srt, to_vtt, SubtitleOptions};
+    use crate::common::batch_response::Word;
+
+    fn word(text: &str, start: f64, end: f64) -> Word {
+        serde_json::from_value(serde_json::json!({
+            "word": text.to_lowercase(),
+            "start": start,
+            "end": end,
+            "confidence": 0.9,
+            "punctuated_word": text,
+        }))
+        .unwrap()
+    }
+
+    fn words() -> Vec<Word> {
+        vec![
+            word("Hello", 0.5, 0.75),
+            word("there.", 0.75, 1.0),
+            word("General", 1.25, 1.75),
+            word("Kenobi!", 1.75, 2.5),
+        ]
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(timestamp(1.0, ','), "00:00:01,000");
+        assert_eq!(timestamp(0.0015, ','), "00:00:00,002");
+        assert_eq!(timestamp(3723.456, '.'), "01:02:03.456");
+    }
+
+    #[test]
+    fn srt() {
+        let opts = SubtitleOptions::default().max_chars(15);
+        assert_eq!(
+            to_srt(&words(), opts),
+            "1\n00:00:00,500 --> 00:00:01,000\nHello there.\n\n\
+             2\n00:00:01,250 --> 00:00:02,500\nGeneral Kenobi!\n\n"
+        );
+    }
+
+    #[test]
+    fn vtt() {
+        let opts = SubtitleOptions::default().max_duration(Duration::from_millis(1500));
+        assert_eq!(
+            to_vtt(&words(), opts),
+            "WEBVTT\n\n\
+             00:00:00.500 --> 00:00:01.750\nHello there. General\n\n\
+             00:00:01.750 --> 00:00:02.500\nKenobi!\n\n"
+        );
+    }
+
+    #[test]
+    fn skips_untimed_words() {
+        let mut words = words();
+        let mut um = word("um", 0.0, 0.8);
+        um.start = f64::NAN;
+        words.insert(1, um);
+        words.insert(2, word("uh", 0.9, 0.7));
+
+        let cues = cues(&words, SubtitleOptions::default());
+        assert_eq!(cues.len(), 1);
+        assert_eq!(cues[0].text, "Hello there. General Kenobi!");
+        assert_eq!((cues[0].start, cues[0].end), (0.5, 2.5));
+        assert_eq!(
+            serde_json::to_string(&cues[0]).unwrap(),
+            r#"{"start":0.5,"end":2.5,"text":"Hello there. General Kenobi!"}"#
+        );
+    }
+}
@@ -0,0 +1,484 @@
+//! Accumulate streaming responses into a running transcript.
+
+use std::collections::VecDeque;
+
+use super::{
+    flux_response::{FluxResponse, TurnEvent},
+    stream_response::{StreamResponse, Word},
+};
+
+/// Keeps track of the transcript of a live stream as responses arrive.
+///
+/// Final results are appended to the transcript, while the latest interim
+/// result is kept aside and replaced by the next one, until it is made final.
+/// Only the first alternative of each result is used.
+///
+/// Feed it the responses of a [`WebsocketHandle`](crate::listen::websocket::WebsocketHandle)
+/// with [`TranscriptBuilder::push`], or the responses of a
+/// [`FluxHandle`](crate::listen::flux::FluxHandle) with
+/// [`TranscriptBuilder::push_flux`].
+///
+/// ```
+/// use deepgram::common::transcript::TranscriptBuilder;
+/// # async fn example(mut handle: deepgram::listen::websocket::WebsocketHandle) {
+/// let mut transcript = TranscriptBuilder::new();
+/// while let Some(Ok(response)) = handle.receive().await {
+///     transcript.push(&response);
+///     println!("{}", transcript.current_text());
+/// }
+/// println!("Final transcript: {}", transcript.final_text());
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TranscriptBuilder {
+    segments: Vec<String>,
+    interim: Option<String>,
+    words: Vec<Word>,
+    duration: f64,
+    terminal_duration: Option<f64>,
+}
+
+impl TranscriptBuilder {
+    /// Start with an empty transcript.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the transcript with a response from a live stream.
+    ///
+    /// The [`StreamResponse::MetadataResponse`] sets the total
+    /// [`TranscriptBuilder::duration`]. Other responses that are not
+    /// transcription results are ignored.
+    pub fn push(&mut self, response: &StreamResponse) {
+        let (is_final, channel) = match response {
+            StreamResponse::TranscriptResponse {
+                is_final,
+                channel,
+                start,
+                duration,
+                ..
+            } => {
+                if *is_final {
+                    self.duration = self.duration.max(start + duration);
+                }
+                (*is_final, channel)
+            }
+            #[allow(deprecated)]
+            StreamResponse::MetadataResponse { duration, .. }
+            | StreamResponse::TerminalResponse { duration, .. } => {
+                self.terminal_duration = Some(*duration);
+                return;
+            }
+            _ => return,
+        };
+        let Some(alternative) = channel.alternatives.first() else {
+            return;
+        };
+
+        if is_final {
+            self.interim = None;
+            self.push_segment(&alternative.transcript);
+            self.words.extend(alternative.words.iter().cloned());
+        } else {
+            self.interim = Some(alternative.transcript.clone());
+        }
+    }
+
+    /// Update the transcript with a response from a Flux stream.
+    ///
+    /// Each turn is appended once it ends. Until then, its latest transcript,
+    /// including after an eager end of turn, is kept as the interim result.
+    /// Flux words carry no timings, so they are not added to
+    /// [`TranscriptBuilder::words`].
+    pub fn push_flux(&mut self, response: &FluxResponse) {
+        let FluxResponse::TurnInfo {
+            event,
+            transcript,
+            audio_window_end,
+            ..
+        } = response
+        else {
+            return;
+        };
+
+        if *event == TurnEvent::EndOfTurn {
+            self.duration = self.duration.max(*audio_window_end);
+            self.interim = None;
+            self.push_segment(transcript);
+        } else {
+            self.interim = Some(transcript.clone());
+        }
+    }
+
+    /// The final transcript followed by the latest interim result, if any.
+    pub fn current_text(&self) -> String {
+        self.join(self.interim.as_deref())
+    }
+
+    /// The transcript made of final results only.
+    pub fn final_text(&self) -> String {
+        self.join(None)
+    }
+
+    /// The words of every final result, in order.
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// The duration of the audio in seconds.
+    ///
+    /// This is the duration reported by Deepgram once the stream has ended,
+    /// and until then the end of the last final result or Flux turn.
+    pub fn duration(&self) -> f64 {
+        self.terminal_duration.unwrap_or(self.duration)
+    }
+
+    /// The average confidence of the final words, weighted by how long each
+    /// word lasts, or [`None`] if there are no words yet.
+    pub fn confidence(&self) -> Option<f64> {
+        if self.words.is_empty() {
+            return None;
+        }
+        let total: f64 = self.words.iter().map(|word| word.end - word.start).sum();
+        if total <= 0.0 {
+            // Words without durations all count the same.
+            let sum: f64 = self.words.iter().map(|word| word.confidence).sum();
+            return Some(sum / self.words.len() as f64);
+        }
+        let weighted: f64 = self
+            .words
+            .iter()
+            .map(|word| word.confidence * (word.end - word.start))
+            .sum();
+        Some(weighted / total)
+    }
+
+    fn push_segment(&mut self, transcript: &str) {
+        let transcript = transcript.trim();
+        if !transcript.is_empty() {
+            self.segments.push(transcript.to_string());
+        }
+    }
+
+    fn join(&self, interim: Option<&str>) -> String {
+        self.segments
+            .iter()
+            .map(String::as_str)
+            .chain(interim.map(str::trim).filter(|interim| !interim.is_empty()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Keeps the best current hypothesis of a live stream whose interim results
+/// overlap, until Deepgram makes it final.
+///
+/// Each interim result replaces the interim results whose audio it overlaps,
+/// since Deepgram revises a hypothesis as more audio arrives. A final result
+/// discards the interim results it overlaps and is queued, to be taken with
+/// [`InterimBuffer::take_final`].
+///
+/// Results of every channel are kept together, so use a buffer per channel
+/// for multichannel streams.
+///
+/// ```
+/// use deepgram::common::transcript::InterimBuffer;
+/// # async fn example(mut handle: deepgram::listen::websocket::WebsocketHandle) {
+/// let mut buffer = InterimBuffer::new();
+/// while let Some(Ok(response)) = handle.receive().await {
+///     buffer.push(response);
+///     while let Some(stable) = buffer.take_final() {
+///         println!("final: {stable:?}");
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct InterimBuffer {
+    // Sorted by start time, without overlaps.
+    interims: Vec<TimedResult>,
+    finals: VecDeque<StreamResponse>,
+}
+
+#[derive(Debug, Clone)]
+struct TimedResult {
+    start: f64,
+    end: f64,
+    response: StreamResponse,
+}
+
+impl TimedResult {
+    fn overlaps(&self, start: f64, end: f64) -> bool {
+        // Results of the same start time overlap even when empty.
+        self.start == start || (self.start < end && start < self.end)
+    }
+}
+
+impl InterimBuffer {
+    /// Start with an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a response from a live stream.
+    ///
+    /// Responses that are not transcription results are ignored.
+    pub fn push(&mut self, response: StreamResponse) {
+        let StreamResponse::TranscriptResponse {
+            start,
+            duration,
+            is_final,
+            speech_final,
+            ..
+        } = response
+        else {
+            return;
+        };
+        let end = start + duration;
+
+        self.interims
+            .retain(|interim| !interim.overlaps(start, end));
+        if is_final || speech_final {
+            self.finals.push_back(response);
+        } else {
+            let index = self
+                .interims
+                .partition_point(|interim| interim.start < start);
+            self.interims.insert(
+                index,
+                TimedResult {
+                    start,
+                    end,
+                    response,
+                },
+            );
+        }
+    }
+
+    /// The latest interim result not made final yet, if any.
+    pub fn current(&self) -> Option<&StreamResponse> {
+        self.interims.last().map(|interim| &interim.response)
+    }
+
+    /// The oldest final result not taken yet, if any.
+    pub fn take_final(&mut self) -> Option<StreamResponse> {
+        self.finals.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InterimBuffer, TranscriptBuilder};
+    use crate::common::{flux_response::FluxResponse, stream_response::StreamResponse};
+
+    fn result(transcript: &str, is_final: bool) -> StreamResponse {
+        let words = transcript
+            .split_whitespace()
+            .enumerate()
+            .map(|(i, word)| {
+                format!(r#"{{"word": "{word}", "start": {i}.0, "end": {i}.5, "confidence": 0.9}}"#)
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        serde_json::from_str(&format!(
+            r#"{{
+                "type": "Results",
+                "start": 0.0,
+                "duration": 1.5,
+                "is_final": {is_final},
+                "speech_final": {is_final},
+                "from_finalize": false,
+                "channel": {{"alternatives": [{{"transcript": "{transcript}", "words": [{words}], "confidence": 0.9}}]}},
+                "metadata": {{
+                    "request_id": "00000000-0000-4000-8000-000000000000",
+                    "model_info": {{"name": "general", "version": "1", "arch": "nova-3"}},
+                    "model_uuid": "00000000-0000-4000-8000-000000000001"
+                }},
+                "channel_index": [0, 1]
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    fn final_result(start: f64, words: &[(&str, f64, f64, f64)]) -> StreamResponse {
+        let transcript = words
+            .iter()
+            .map(|(word, ..)| *word)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let words = words
+            .iter()
+            .map(|(word, start, end, confidence)| {
+                format!(
+                    r#"{{"word": "{word}", "start": {start}, "end": {end}, "confidence": {confidence}}}"#
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        serde_json::from_str(&format!(
+            r#"{{
+                "type": "Results",
+                "start": {start},
+                "duration": 1.0,
+                "is_final": true,
+                "speech_final": true,
+                "from_finalize": false,
+                "channel": {{"alternatives": [{{"transcript": "{transcript}", "words": [{words}], "confidence": 0.9}}]}},
+                "metadata": {{
+                    "request_id": "00000000-0000-4000-8000-000000000000",
+                    "model_info": {{"name": "general", "version": "1", "arch": "nova-3"}},
+                    "model_uuid": "00000000-0000-4000-8000-000000000001"
+                }},
+                "channel_index": [0, 1]
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    fn turn(event: &str, transcript: &str) -> FluxResponse {
+        serde_json::from_str(&format!(
+            r#"{{
+                "type": "TurnInfo",
+                "request_id": "00000000-0000-4000-8000-000000000000",
+                "sequence_id": 1,
+                "event": "{event}",
+                "turn_index": 0,
+                "audio_window_start": 0.0,
+                "audio_window_end": 1.5,
+                "transcript": "{transcript}",
+                "words": [],
+                "end_of_turn_confidence": 0.9
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn interim_then_final() {
+        let mut transcript = TranscriptBuilder::new();
+        assert_eq!(transcript.current_text(), "");
+
+        transcript.push(&result("hel", false));
+        assert_eq!(transcript.current_text(), "hel");
+        assert_eq!(transcript.final_text(), "");
+
+        transcript.push(&result("hello", false));
+        assert_eq!(transcript.current_text(), "hello");
+
+        transcript.push(&result("hello world", true));
+        assert_eq!(transcript.current_text(), "hello world");
+        assert_eq!(transcript.final_text(), "hello world");
+
+        transcript.push(&result("how", false));
+        assert_eq!(transcript.current_text(), "hello world how");
+        assert_eq!(transcript.final_text(), "hello world");
+
+        transcript.push(&result("", true));
+        transcript.push(&result("how are you", true));
+        assert_eq!(transcript.current_text(), "hello world how are you");
+        assert_eq!(transcript.final_text(), "hello world how are you");
+
+        let words: Vec<_> = transcript.words().iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["hello", "world", "how", "are", "you"]);
+    }
+
+    #[test]
+    fn flux_turns() {
+        let mut transcript = TranscriptBuilder::new();
+
+        transcript.push_flux(&turn("StartOfTurn", ""));
+        transcript.push_flux(&turn("Update", "hello"));
+        assert_eq!(transcript.current_text(), "hello");
+
+        transcript.push_flux(&turn("EagerEndOfTurn", "hello there"));
+        transcript.push_flux(&turn("TurnResumed", "hello there"));
+        assert_eq!(transcript.final_text(), "");
+
+        transcript.push_flux(&turn("EndOfTurn", "hello there friend"));
+        transcript.push_flux(&turn("Update", "bye"));
+        assert_eq!(transcript.current_text(), "hello there friend bye");
+        assert_eq!(transcript.final_text(), "hello there friend");
+        assert!(transcript.words().is_empty());
+        assert_eq!(transcript.duration(), 1.5);
+    }
+
+    #[test]
+    fn duration_and_confidence() {
+        let mut transcript = TranscriptBuilder::new();
+        assert_eq!(transcript.duration(), 0.0);
+        assert_eq!(transcript.confidence(), None);
+
+        transcript.push(&final_result(0.0, &[("hello", 0.0, 0.5, 1.0)]));
+        transcript.push(&result("interim", false));
+        transcript.push(&final_result(
+            1.0,
+            &[("how", 1.0, 1.25, 0.5), ("are", 1.25, 1.5, 0.5)],
+        ));
+        transcript.push(&final_result(2.0, &[("you", 2.0, 3.0, 0.8)]));
+
+        // The end of the last final result, until Deepgram reports the total.
+        assert_eq!(transcript.duration(), 3.0);
+        // (1.0 * 0.5 + 0.5 * 0.25 + 0.5 * 0.25 + 0.8 * 1.0) / 2.0
+        let confidence = transcript.confidence().unwrap();
+        assert!((confidence - 0.775).abs() < 1e-9, "{confidence}");
+
+        let terminal = serde_json::from_str(
+            r#"{
+                "type": "Metadata",
+                "request_id": "00000000-0000-4000-8000-000000000000",
+                "created": "2025-01-01T00:00:00.000Z",
+                "duration": 3.5,
+                "channels": 1
+            }"#,
+        )
+        .unwrap();
+        transcript.push(&terminal);
+        assert_eq!(transcript.duration(), 3.5);
+    }
+
+    fn timed(start: f64, duration: f64, transcript: &str, is_final: bool) -> StreamResponse {
+        let mut response = result(transcript, is_final);
+        if let StreamResponse::TranscriptResponse {
+            start: response_start,
+            duration: response_duration,
+            ..
+        } = &mut response
+        {
+            *response_start = start;
+            *response_duration = duration;
+        }
+        response
+    }
+
+    fn text(response: &StreamResponse) -> &str {
+        match response {
+            StreamResponse::TranscriptResponse { channel, .. } => {
+                &channel.alternatives[0].transcript
+            }
+            response => panic!("unexpected response: {response:?}"),
+        }
+    }
+
+    #[test]
+    fn interim_buffer() {
+        let mut buffer = InterimBuffer::new();
+        assert!(buffer.current().is_none());
+
+        buffer.push(timed(0.0, 0.5, "hel", false));
+        buffer.push(timed(0.0, 1.0, "hello", false));
+        assert_eq!(text(buffer.current().unwrap()), "hello");
+        // Overlaps the previous hypothesis, so replaces it.
+        buffer.push(timed(0.5, 1.0, "hello wor", false));
+        assert_eq!(text(buffer.current().unwrap()), "hello wor");
+        assert!(buffer.take_final().is_none());
+
+        buffer.push(timed(0.0, 2.0, "hello world", true));
+        assert!(buffer.current().is_none());
+        buffer.push(timed(2.0, 0.5, "how", false));
+        assert_eq!(text(buffer.current().unwrap()), "how");
+
+        assert_eq!(text(&buffer.take_final().unwrap()), "hello world");
+        assert!(buffer.take_final().is_none());
+        assert_eq!(text(buffer.current().unwrap()), "how");
+    }
+}
@@ -13,17 +13,22 @@ pub use serde_json::Error as SerdeJsonError;
 pub use serde_urlencoded::ser::Error as SerdeUrlencodedError;
 use std::io;
 use std::ops::Deref;
-#[cfg(feature = "listen")]
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(any(feature = "listen", feature = "speak"))]
 pub use tungstenite::Error as TungsteniteError;
 
 use reqwest::{
-    header::{HeaderMap, HeaderValue},
-    RequestBuilder,
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, USER_AGENT},
+    IntoUrl, RequestBuilder,
 };
 use serde::de::DeserializeOwned;
 use thiserror::Error;
 use url::Url;
 
+use crate::proxy::ProxySetting;
+pub use crate::retry::RetryPolicy;
+
 pub mod auth;
 #[cfg(feature = "listen")]
 pub mod common;
@@ -33,9 +38,20 @@ pub mod listen;
 pub mod manage;
 #[cfg(feature = "speak")]
 pub mod speak;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+#[cfg(test)]
+mod mock_server;
+mod proxy;
+mod retry;
+#[cfg(any(feature = "listen", feature = "speak"))]
+mod websocket;
 
 static DEEPGRAM_BASE_URL: &str = "https://api.deepgram.com";
 
+static DEFAULT_USER_AGENT: &str = concat!("deepgram-rust-sdk/", env!("CARGO_PKG_VERSION"));
+
 /// Transcribe audio using Deepgram's automated speech recognition.
 ///
 /// Constructed using [`Deepgram::transcription`].
@@ -129,17 +145,145 @@ impl AuthMethod {
     }
 }
 
+/// The scheme of the `Authorization` header sent with the credentials.
+///
+/// See [`DeepgramBuilder::auth_scheme`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum AuthScheme {
+    /// `Authorization: Token <key>`, which Deepgram's hosted API expects
+    /// for API keys.
+    #[default]
+    Token,
+
+    /// `Authorization: Bearer <key>`, used for temporary tokens and
+    /// expected by some self-hosted deployments and gateways.
+    Bearer,
+}
+
+impl AuthMethod {
+    /// The same credentials, sent with `scheme`.
+    fn with_scheme(self, scheme: AuthScheme) -> Self {
+        let (AuthMethod::ApiKey(secret) | AuthMethod::TempToken(secret)) = self;
+        match scheme {
+            AuthScheme::Token => AuthMethod::ApiKey(secret),
+            AuthScheme::Bearer => AuthMethod::TempToken(secret),
+        }
+    }
+}
+
 /// A client for the Deepgram API.
 ///
 /// Make transcriptions requests using [`Deepgram::transcription`].
+///
+/// Cloning a client is cheap: clones share its configuration and its pool
+/// of HTTP connections, so a single client can be kept in application state
+/// and cloned for each handler.
 #[derive(Debug, Clone)]
 pub struct Deepgram {
+    inner: Arc<DeepgramInner>,
+}
+
+/// The HTTP client requests are sent with.
+#[derive(Debug)]
+struct HttpClient {
+    client: reqwest::Client,
+    /// Headers added to every request, for clients supplied with
+    /// [`DeepgramBuilder::client`] which don't send them by default.
+    headers: Option<HeaderMap>,
+}
+
+impl HttpClient {
+    fn request(&self, method: reqwest::Method, url: impl IntoUrl) -> RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.headers {
+            Some(headers) => request.headers(headers.clone()),
+            None => request,
+        }
+    }
+
+    #[cfg_attr(not(feature = "manage"), allow(dead_code))]
+    pub(crate) fn get(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(reqwest::Method::GET, url)
+    }
+
+    pub(crate) fn post(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(reqwest::Method::POST, url)
+    }
+
+    #[cfg_attr(not(feature = "manage"), allow(dead_code))]
+    pub(crate) fn put(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(reqwest::Method::PUT, url)
+    }
+
+    #[cfg_attr(not(feature = "manage"), allow(dead_code))]
+    pub(crate) fn patch(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(reqwest::Method::PATCH, url)
+    }
+
+    #[cfg_attr(not(feature = "manage"), allow(dead_code))]
+    pub(crate) fn delete(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(reqwest::Method::DELETE, url)
+    }
+}
+
+/// The configuration shared by clones of a [`Deepgram`] client.
+#[derive(Debug)]
+struct DeepgramInner {
     #[cfg_attr(not(feature = "listen"), allow(unused))]
     auth: Option<AuthMethod>,
     #[cfg_attr(not(feature = "listen"), allow(unused))]
     base_url: Url,
+    client: HttpClient,
+    /// Custom headers sent with every request, not including `Authorization`.
     #[cfg_attr(not(feature = "listen"), allow(unused))]
-    client: reqwest::Client,
+    headers: HeaderMap,
+    #[cfg_attr(not(feature = "listen"), allow(unused))]
+    proxy: Option<ProxySetting>,
+    #[cfg_attr(not(feature = "listen"), allow(unused))]
+    retry: Option<RetryPolicy>,
+    /// Bounds opening websocket connections, up to the end of the upgrade.
+    #[cfg_attr(not(any(feature = "listen", feature = "speak")), allow(unused))]
+    connect_timeout: Option<Duration>,
+    /// Sent with every HTTP request and websocket upgrade.
+    #[cfg_attr(not(any(feature = "listen", feature = "speak")), allow(unused))]
+    user_agent: String,
+    /// Admin endpoints always go to the hosted API, whatever `base_url` is.
+    #[cfg_attr(not(feature = "manage"), allow(unused))]
+    manage_base_url: Url,
+}
+
+/// Builds a [`Deepgram`] client with settings not covered by its constructors.
+///
+/// Constructed using [`Deepgram::builder`].
+///
+/// # Example
+///
+/// ```
+/// # use deepgram::Deepgram;
+/// use reqwest::header::{HeaderName, HeaderValue};
+///
+/// let deepgram = Deepgram::builder()
+///     .api_key("apikey12345")
+///     .header(
+///         HeaderName::from_static("x-tenant-id"),
+///         HeaderValue::from_static("acme"),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeepgramBuilder {
+    base_url: Url,
+    auth: Option<AuthMethod>,
+    headers: HeaderMap,
+    proxy: Option<ProxySetting>,
+    auth_scheme: Option<AuthScheme>,
+    client: Option<reqwest::Client>,
+    retry: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
 }
 
 /// Errors that may arise from the [`deepgram`](crate) crate.
@@ -148,13 +292,13 @@ pub struct Deepgram {
 #[non_exhaustive]
 pub enum DeepgramError {
     /// The Deepgram API returned an error.
-    #[error("The Deepgram API returned an error.")]
+    #[error("The Deepgram API returned an error with status {status}: {body}")]
     DeepgramApiError {
-        /// Error message from the Deepgram API.
-        body: String,
+        /// HTTP status code of the response.
+        status: u16,
 
-        /// Underlying [`reqwest::Error`] from the HTTP request.
-        err: ReqwestError,
+        /// Error details from the Deepgram API.
+        body: DeepgramApiErrorBody,
     },
 
     /// Something went wrong when generating the http request.
@@ -163,13 +307,29 @@ pub enum DeepgramError {
 
     /// Something went wrong when making the HTTP request.
     #[error("Something went wrong when making the HTTP request: {0}")]
-    ReqwestError(#[from] ReqwestError),
+    ReqwestError(ReqwestError),
+
+    /// The HTTP request did not complete within the configured timeout.
+    ///
+    /// See [`DeepgramBuilder::timeout`].
+    #[error("The HTTP request timed out: {0}")]
+    Timeout(ReqwestError),
+
+    /// No message arrived from Deepgram within the given time.
+    #[error("No message was received within {0:?}")]
+    ReceiveTimeout(Duration),
+
+    /// A websocket connection could not be opened within the given time.
+    ///
+    /// See [`DeepgramBuilder::connect_timeout`].
+    #[error("Could not connect within {0:?}")]
+    ConnectTimeout(Duration),
 
     /// Something went wrong during I/O.
     #[error("Something went wrong during I/O: {0}")]
     IoError(#[from] io::Error),
 
-    #[cfg(feature = "listen")]
+    #[cfg(any(feature = "listen", feature = "speak"))]
     /// Something went wrong with WS.
     #[error("Something went wrong with WS: {0}")]
     WsError(#[from] Box<TungsteniteError>),
@@ -178,6 +338,15 @@ pub enum DeepgramError {
     #[error("Something went wrong during json serialization/deserialization: {0}")]
     JsonError(#[from] SerdeJsonError),
 
+    /// A message received from Deepgram could not be parsed.
+    #[error("Failed to parse a message from Deepgram: {source}")]
+    DeserializationError {
+        /// Why the message could not be parsed
+        source: SerdeJsonError,
+        /// The text of the message
+        raw: String,
+    },
+
     /// Something went wrong during serialization/deserialization.
     #[error("Something went wrong during query serialization: {0}")]
     UrlencodedError(#[from] SerdeUrlencodedError),
@@ -190,6 +359,20 @@ pub enum DeepgramError {
     #[error("The provided base url is not valid")]
     InvalidUrl,
 
+    /// The API key is empty or only whitespace.
+    #[error("The API key is empty")]
+    InvalidApiKey,
+
+    /// A required environment variable is not set or is not valid unicode.
+    ///
+    /// See [`Deepgram::from_env`].
+    #[error("The environment variable {0} is not set")]
+    MissingEnvVar(&'static str),
+
+    /// The request options are inconsistent and would be rejected by Deepgram.
+    #[error("The request options are not valid: {0}")]
+    InvalidOptions(String),
+
     /// A websocket close from was received indicating an error
     #[error("websocket close frame received with error content: code: {code}, reason: {reason}")]
     WebsocketClose {
@@ -199,6 +382,19 @@ pub enum DeepgramError {
         reason: String,
     },
 
+    /// A Flux message arrived with a `sequence_id` other than the one
+    /// expected next, so messages were dropped or reordered.
+    ///
+    /// See [`FluxBuilder::detect_sequence_gaps`](crate::listen::flux::FluxBuilder::detect_sequence_gaps).
+    #[cfg(feature = "listen")]
+    #[error("Expected a message with sequence ID {expected} but received {got}")]
+    SequenceGap {
+        /// The sequence ID following the highest one received so far.
+        expected: u32,
+        /// The sequence ID of the message received.
+        got: u32,
+    },
+
     /// An unexpected error occurred in the client
     #[error("an unepected error occurred in the deepgram client: {0}")]
     InternalClientError(anyhow::Error),
@@ -208,7 +404,72 @@ pub enum DeepgramError {
     UnexpectedServerResponse(anyhow::Error),
 }
 
-#[cfg(feature = "listen")]
+impl From<ReqwestError> for DeepgramError {
+    fn from(err: ReqwestError) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err)
+        } else {
+            Self::ReqwestError(err)
+        }
+    }
+}
+
+/// The body of an error response from the Deepgram API.
+///
+/// Responses that are not in Deepgram's JSON error format, such as those of
+/// an intermediate proxy, only have [`DeepgramApiErrorBody::raw`] set.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Deserialize)]
+#[non_exhaustive]
+pub struct DeepgramApiErrorBody {
+    /// Short machine-readable error code, such as `Bad Request`.
+    pub err_code: Option<String>,
+
+    /// Human-readable description of the error.
+    pub err_msg: Option<String>,
+
+    /// The Deepgram request ID, to provide when contacting support.
+    pub request_id: Option<String>,
+
+    /// The response body as it was received.
+    #[serde(skip)]
+    pub raw: String,
+}
+
+impl DeepgramApiErrorBody {
+    fn parse(raw: String) -> Self {
+        Self {
+            raw: raw.clone(),
+            ..serde_json::from_str(&raw).unwrap_or_default()
+        }
+    }
+}
+
+impl fmt::Display for DeepgramApiErrorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.err_code, &self.err_msg) {
+            (Some(code), Some(msg)) => write!(f, "{code}: {msg}"),
+            (None, Some(msg)) => f.write_str(msg),
+            _ => f.write_str(&self.raw),
+        }
+    }
+}
+
+impl DeepgramError {
+    /// Read an unsuccessful response into a [`DeepgramError::DeepgramApiError`].
+    #[cfg_attr(not(any(feature = "listen", feature = "speak")), allow(unused))]
+    async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status().as_u16();
+        match response.text().await {
+            Ok(raw) => DeepgramError::DeepgramApiError {
+                status,
+                body: DeepgramApiErrorBody::parse(raw),
+            },
+            Err(err) => err.into(),
+        }
+    }
+}
+
+#[cfg(any(feature = "listen", feature = "speak"))]
 impl From<TungsteniteError> for DeepgramError {
     fn from(err: TungsteniteError) -> Self {
         Self::from(Box::new(err))
@@ -227,15 +488,43 @@ impl Deepgram {
     ///
     /// [console]: https://console.deepgram.com/
     ///
+    /// Whitespace surrounding the key is ignored.
+    ///
     /// # Errors
     ///
-    /// Errors under the same conditions as [`reqwest::ClientBuilder::build`].
+    /// Returns [`DeepgramError::InvalidApiKey`] if `api_key` is empty or only
+    /// whitespace, and otherwise errors under the same conditions as
+    /// [`reqwest::ClientBuilder::build`].
     pub fn new<K: AsRef<str>>(api_key: K) -> Result<Self> {
         let auth = AuthMethod::ApiKey(RedactedString(api_key.as_ref().to_owned()));
-        // This cannot panic because we are converting a static value
-        // that is known-good.
-        let base_url = DEEPGRAM_BASE_URL.try_into().unwrap();
-        Self::inner_constructor(base_url, Some(auth))
+        Self::inner_constructor(DeepgramBuilder {
+            auth: Some(auth),
+            ..Self::builder()
+        })
+    }
+
+    /// Construct a new Deepgram client with an API key, sending HTTP requests
+    /// with `client`.
+    ///
+    /// See [`DeepgramBuilder::client`] for how the client is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeepgramError::InvalidApiKey`] if `api_key` is empty or only
+    /// whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use deepgram::Deepgram;
+    /// let http = reqwest::Client::builder()
+    ///     .pool_max_idle_per_host(4)
+    ///     .build()
+    ///     .unwrap();
+    /// let deepgram = Deepgram::with_client("apikey12345", http).unwrap();
+    /// ```
+    pub fn with_client<K: AsRef<str>>(api_key: K, client: reqwest::Client) -> Result<Self> {
+        Self::builder().api_key(api_key).client(client).build()
     }
 
     /// Construct a new Deepgram client with a temporary token.
@@ -243,8 +532,10 @@ impl Deepgram {
     /// This uses the "Bearer" prefix for authentication, suitable for temporary tokens.
     pub fn with_temp_token<T: AsRef<str>>(temp_token: T) -> Result<Self> {
         let auth = AuthMethod::TempToken(RedactedString(temp_token.as_ref().to_owned()));
-        let base_url = DEEPGRAM_BASE_URL.try_into().unwrap();
-        Self::inner_constructor(base_url, Some(auth))
+        Self::inner_constructor(DeepgramBuilder {
+            auth: Some(auth),
+            ..Self::builder()
+        })
     }
 
     /// Construct a new Deepgram client with the specified base URL.
@@ -284,7 +575,7 @@ impl Deepgram {
         U::Error: std::fmt::Debug,
     {
         let base_url = base_url.try_into().map_err(|_| DeepgramError::InvalidUrl)?;
-        Self::inner_constructor(base_url, None)
+        Self::inner_constructor(Self::builder().base_url(base_url))
     }
 
     /// Construct a new Deepgram client with the specified base URL and
@@ -322,7 +613,11 @@ impl Deepgram {
     {
         let base_url = base_url.try_into().map_err(|_| DeepgramError::InvalidUrl)?;
         let auth = AuthMethod::ApiKey(RedactedString(api_key.as_ref().to_owned()));
-        Self::inner_constructor(base_url, Some(auth))
+        Self::inner_constructor(DeepgramBuilder {
+            base_url,
+            auth: Some(auth),
+            ..Self::builder()
+        })
     }
 
     /// Construct a new Deepgram client with the specified base URL and temp token.
@@ -334,40 +629,362 @@ impl Deepgram {
     {
         let base_url = base_url.try_into().map_err(|_| DeepgramError::InvalidUrl)?;
         let auth = AuthMethod::TempToken(RedactedString(temp_token.as_ref().to_owned()));
-        Self::inner_constructor(base_url, Some(auth))
+        Self::inner_constructor(DeepgramBuilder {
+            base_url,
+            auth: Some(auth),
+            ..Self::builder()
+        })
     }
 
-    fn inner_constructor(base_url: Url, auth: Option<AuthMethod>) -> Result<Self> {
-        static USER_AGENT: &str = concat!(
-            env!("CARGO_PKG_NAME"),
-            "/",
-            env!("CARGO_PKG_VERSION"),
-            " rust",
-        );
+    /// Construct a new Deepgram client from the environment.
+    ///
+    /// The API key is read from `DEEPGRAM_API_KEY`. If `DEEPGRAM_BASE_URL` is
+    /// set, the client is pointed at that URL instead of Deepgram's hosted
+    /// API, as with [`Deepgram::with_base_url_and_api_key`]. HTTP requests and
+    /// websocket connections honour the proxy environment variables, as with
+    /// [`DeepgramBuilder::proxy_from_env`].
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// # use deepgram::Deepgram;
+    /// # fn main() -> Result<(), deepgram::DeepgramError> {
+    /// let deepgram = Deepgram::from_env()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeepgramError::MissingEnvVar`] if `DEEPGRAM_API_KEY` is not
+    /// set, [`DeepgramError::InvalidUrl`] if `DEEPGRAM_BASE_URL` is not a
+    /// valid URL, and otherwise errors under the same conditions as
+    /// [`reqwest::ClientBuilder::build`].
+    pub fn from_env() -> Result<Self> {
+        Self::from_env_with(|name| std::env::var_os(name))
+    }
+
+    /// [`Deepgram::from_env`], reading variables through `var` so tests don't
+    /// have to mutate the process environment.
+    fn from_env_with(var: impl Fn(&str) -> Option<std::ffi::OsString>) -> Result<Self> {
+        let api_key = var("DEEPGRAM_API_KEY")
+            .and_then(|api_key| api_key.into_string().ok())
+            .ok_or(DeepgramError::MissingEnvVar("DEEPGRAM_API_KEY"))?;
+        let mut builder = Self::builder().api_key(api_key).proxy_from_env();
+        if let Some(base_url) = var("DEEPGRAM_BASE_URL") {
+            let base_url = base_url
+                .to_str()
+                .and_then(|base_url| Url::parse(base_url).ok())
+                .ok_or(DeepgramError::InvalidUrl)?;
+            builder = builder.base_url(base_url);
+        }
+        builder.build()
+    }
+
+    /// Begin to configure a client pointed at Deepgram's hosted API,
+    /// without any credentials.
+    pub fn builder() -> DeepgramBuilder {
+        DeepgramBuilder {
+            // This cannot panic because we are converting a static value
+            // that is known-good.
+            base_url: DEEPGRAM_BASE_URL.try_into().unwrap(),
+            auth: None,
+            headers: HeaderMap::new(),
+            proxy: None,
+            auth_scheme: None,
+            client: None,
+            retry: None,
+            timeout: None,
+            connect_timeout: None,
+            user_agent: None,
+        }
+    }
+
+    fn inner_constructor(builder: DeepgramBuilder) -> Result<Self> {
+        let DeepgramBuilder {
+            base_url,
+            mut auth,
+            headers,
+            proxy,
+            auth_scheme,
+            client: custom_client,
+            retry,
+            timeout,
+            connect_timeout,
+            user_agent,
+        } = builder;
+        // A supplied client keeps its own User-Agent unless one was set here.
+        let custom_user_agent = user_agent.clone();
+        let user_agent = user_agent.unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
 
         if base_url.cannot_be_a_base() {
             return Err(DeepgramError::InvalidUrl);
         }
-        let authorization_header = {
-            let mut header = HeaderMap::new();
+        if let Some(AuthMethod::ApiKey(api_key)) = &mut auth {
+            // Keys pasted from a terminal or a config file often carry a newline.
+            let trimmed = api_key.trim();
+            if trimmed.is_empty() {
+                return Err(DeepgramError::InvalidApiKey);
+            }
+            if trimmed.len() != api_key.len() {
+                api_key.0 = trimmed.to_owned();
+            }
+        }
+        if let Some(scheme) = auth_scheme {
+            auth = auth.map(|auth| auth.with_scheme(scheme));
+        }
+        let default_headers = {
+            // Custom headers go in first so they can never replace the credentials.
+            let mut header = headers.clone();
+            header.remove(AUTHORIZATION);
             if let Some(auth) = &auth {
                 let header_value = auth.header_value();
                 if let Ok(value) = HeaderValue::from_str(&header_value) {
-                    header.insert("Authorization", value);
+                    header.insert(AUTHORIZATION, value);
                 }
             }
             header
         };
 
+        let client = match custom_client {
+            // A supplied client is used as is, so the headers go with each request.
+            Some(client) => {
+                let mut default_headers = default_headers;
+                if let Some(user_agent) = &custom_user_agent {
+                    let user_agent = HeaderValue::from_str(user_agent).map_err(HttpError::from)?;
+                    default_headers.insert(USER_AGENT, user_agent);
+                }
+                HttpClient {
+                    client,
+                    headers: Some(default_headers),
+                }
+            }
+            None => HttpClient {
+                client: Self::http_client(
+                    default_headers,
+                    &user_agent,
+                    timeout,
+                    connect_timeout,
+                    proxy.as_ref(),
+                )?,
+                headers: None,
+            },
+        };
+
         Ok(Deepgram {
-            auth,
-            base_url,
-            client: reqwest::Client::builder()
-                .user_agent(USER_AGENT)
-                .default_headers(authorization_header)
-                .build()?,
+            inner: Arc::new(DeepgramInner {
+                auth,
+                base_url,
+                client,
+                headers,
+                proxy,
+                retry,
+                connect_timeout,
+                user_agent,
+                // This cannot panic because we are converting a static value
+                // that is known-good.
+                manage_base_url: DEEPGRAM_BASE_URL.try_into().unwrap(),
+            }),
         })
     }
+
+    fn http_client(
+        default_headers: HeaderMap,
+        user_agent: &str,
+        timeout: Option<Duration>,
+        connect_timeout: Option<Duration>,
+        proxy: Option<&ProxySetting>,
+    ) -> Result<reqwest::Client> {
+        let mut client = reqwest::Client::builder()
+            .user_agent(user_agent)
+            .default_headers(default_headers);
+        // Match the backend tokio-tungstenite picks for websockets, which
+        // prefers native-tls when both are enabled.
+        #[cfg(feature = "native-tls")]
+        {
+            client = client.use_native_tls();
+        }
+        #[cfg(all(feature = "rustls-tls", not(feature = "native-tls")))]
+        {
+            client = client.use_rustls_tls();
+        }
+        if let Some(timeout) = timeout {
+            client = client.timeout(timeout);
+        }
+        if let Some(connect_timeout) = connect_timeout {
+            client = client.connect_timeout(connect_timeout);
+        }
+        // Without an explicit proxy reqwest already honours the proxy environment variables.
+        if let Some(ProxySetting::Url(proxy)) = proxy {
+            client = client.proxy(reqwest::Proxy::all(proxy.as_str())?);
+        }
+        Ok(client.build()?)
+    }
+
+    /// The URL of a management API endpoint, such as `v1/projects`.
+    #[cfg(feature = "manage")]
+    pub(crate) fn manage_url(&self, path: &str) -> Url {
+        self.inner
+            .manage_base_url
+            .join(path)
+            .expect("management API paths are valid relative URLs")
+    }
+}
+
+impl DeepgramBuilder {
+    /// Authenticate with an API key, using the `Token` scheme.
+    pub fn api_key<K: AsRef<str>>(mut self, api_key: K) -> Self {
+        self.auth = Some(AuthMethod::ApiKey(RedactedString(
+            api_key.as_ref().to_owned(),
+        )));
+        self
+    }
+
+    /// Authenticate with a temporary token, using the `Bearer` scheme.
+    pub fn temp_token<T: AsRef<str>>(mut self, temp_token: T) -> Self {
+        self.auth = Some(AuthMethod::TempToken(RedactedString(
+            temp_token.as_ref().to_owned(),
+        )));
+        self
+    }
+
+    /// Send the credentials with `scheme`, whichever of
+    /// [`DeepgramBuilder::api_key`] and [`DeepgramBuilder::temp_token`] set
+    /// them.
+    ///
+    /// API keys default to [`AuthScheme::Token`], as Deepgram's hosted API
+    /// expects, and temporary tokens to [`AuthScheme::Bearer`]. Self-hosted
+    /// deployments and gateways may expect API keys as bearer tokens.
+    ///
+    /// ```
+    /// # use deepgram::{AuthScheme, Deepgram};
+    /// let deepgram = Deepgram::builder()
+    ///     .api_key("apikey12345")
+    ///     .auth_scheme(AuthScheme::Bearer)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn auth_scheme(mut self, scheme: AuthScheme) -> Self {
+        self.auth_scheme = Some(scheme);
+        self
+    }
+
+    /// Point the client at a self-hosted instance instead of Deepgram's hosted API.
+    ///
+    /// See [`Deepgram::with_base_url`] for details.
+    pub fn base_url(mut self, base_url: Url) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Send an additional header with every HTTP request and websocket upgrade.
+    ///
+    /// Useful for gateways that route on headers such as `X-Tenant-Id`.
+    /// Custom headers never replace the `Authorization` header, nor the headers
+    /// required by the websocket handshake.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Send additional headers with every request.
+    ///
+    /// See [`DeepgramBuilder::header`].
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Send HTTP requests and websocket connections through a proxy.
+    ///
+    /// Websocket connections are tunnelled using HTTP `CONNECT`. Credentials
+    /// embedded in the proxy URL are sent as basic proxy authorization.
+    pub fn proxy(mut self, proxy: Url) -> Self {
+        self.proxy = Some(ProxySetting::Url(proxy));
+        self
+    }
+
+    /// Pick the proxy from the `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and
+    /// `NO_PROXY` environment variables, for websocket connections as well.
+    ///
+    /// HTTP requests honour these variables even without calling this method.
+    pub fn proxy_from_env(mut self) -> Self {
+        self.proxy = Some(ProxySetting::Env);
+        self
+    }
+
+    /// Send HTTP requests with `client`, for instance to share its connection
+    /// pool, TLS configuration or middleware with the rest of the application.
+    ///
+    /// The credentials and custom headers are added to each request, as is
+    /// the user agent if one is set with [`DeepgramBuilder::user_agent`]. The
+    /// client is used as is otherwise, so [`DeepgramBuilder::timeout`],
+    /// [`DeepgramBuilder::connect_timeout`] and [`DeepgramBuilder::proxy`] don't
+    /// apply to its requests. Websocket connections don't go through `client`,
+    /// but use this builder's settings.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Retry pre-recorded transcription requests that fail for transient
+    /// reasons, such as rate limiting or a temporarily unavailable service.
+    ///
+    /// See [`RetryPolicy`] for which failures are retried. A single request
+    /// can use another policy with
+    /// [`PrerecordedBuilder::retry`](crate::listen::rest::PrerecordedBuilder::retry).
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Fail HTTP requests that take longer than `timeout` to complete with
+    /// [`DeepgramError::Timeout`].
+    ///
+    /// The timeout covers the whole request, from connecting until the
+    /// response body has been read, so transcribing long files needs a
+    /// generous one. By default requests never time out. Websocket
+    /// connections are not affected.
+    ///
+    /// A single pre-recorded transcription request can use another timeout
+    /// with [`PrerecordedBuilder::timeout`](crate::listen::rest::PrerecordedBuilder::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fail websocket connections that are not open within `timeout` with
+    /// [`DeepgramError::ConnectTimeout`].
+    ///
+    /// The timeout covers dialing, any proxy tunnel, the TLS handshake and
+    /// the websocket upgrade, but not the messages exchanged afterwards.
+    /// HTTP requests that cannot connect in time fail with
+    /// [`DeepgramError::Timeout`]. By default connecting never times out.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Identify the application in the `User-Agent` header of every HTTP
+    /// request and websocket upgrade.
+    ///
+    /// Defaults to `deepgram-rust-sdk/<version>`. Integrations embedding the
+    /// SDK can set their own, so Deepgram support can tell their traffic apart.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Finish building the [`Deepgram`] client.
+    ///
+    /// # Errors
+    ///
+    /// Errors under the same conditions as [`reqwest::ClientBuilder::build`], if
+    /// the base URL is not a valid base URL, or with
+    /// [`DeepgramError::InvalidApiKey`] if the API key is empty.
+    pub fn build(self) -> Result<Deepgram> {
+        Deepgram::inner_constructor(self)
+    }
 }
 
 /// Sends the request and checks the response for an error.
@@ -378,14 +995,16 @@ impl Deepgram {
 async fn send_and_translate_response<R: DeserializeOwned>(
     request_builder: RequestBuilder,
 ) -> crate::Result<R> {
-    let response = request_builder.send().await?;
+    translate_response(request_builder.send().await?).await
+}
 
-    match response.error_for_status_ref() {
-        Ok(_) => Ok(response.json().await?),
-        Err(err) => Err(DeepgramError::DeepgramApiError {
-            body: response.text().await?,
-            err,
-        }),
+/// Checks the response for an error, as in [`send_and_translate_response`].
+#[cfg_attr(not(feature = "listen"), allow(unused))]
+async fn translate_response<R: DeserializeOwned>(response: reqwest::Response) -> crate::Result<R> {
+    if response.status().is_success() {
+        Ok(response.json().await?)
+    } else {
+        Err(DeepgramError::from_response(response).await)
     }
 }
 
@@ -405,11 +1024,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deepgram_from_env() {
+        fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<std::ffi::OsString> + 'a {
+            move |name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.into())
+            }
+        }
+
+        assert!(matches!(
+            Deepgram::from_env_with(env(&[])),
+            Err(DeepgramError::MissingEnvVar("DEEPGRAM_API_KEY"))
+        ));
+
+        let client = Deepgram::from_env_with(env(&[("DEEPGRAM_API_KEY", "test_api_key")])).unwrap();
+        assert_eq!(
+            client.inner.auth,
+            Some(AuthMethod::ApiKey(RedactedString(
+                "test_api_key".to_string()
+            )))
+        );
+        assert_eq!(client.inner.base_url.as_str(), "https://api.deepgram.com/");
+        assert_eq!(client.inner.proxy, Some(ProxySetting::Env));
+
+        let client = Deepgram::from_env_with(env(&[
+            ("DEEPGRAM_API_KEY", "test_api_key"),
+            ("DEEPGRAM_BASE_URL", "http://localhost:8080"),
+        ]))
+        .unwrap();
+        assert_eq!(client.inner.base_url.as_str(), "http://localhost:8080/");
+
+        assert!(matches!(
+            Deepgram::from_env_with(env(&[
+                ("DEEPGRAM_API_KEY", "test_api_key"),
+                ("DEEPGRAM_BASE_URL", "not a url"),
+            ])),
+            Err(DeepgramError::InvalidUrl)
+        ));
+
+        assert!(matches!(
+            Deepgram::from_env_with(env(&[("DEEPGRAM_BASE_URL", "http://localhost:8080")])),
+            Err(DeepgramError::MissingEnvVar("DEEPGRAM_API_KEY"))
+        ));
+    }
+
     #[test]
     fn test_deepgram_new_with_temp_token() {
         let client = Deepgram::with_temp_token("test_temp_token").unwrap();
         assert_eq!(
-            client.auth,
+            client.inner.auth,
             Some(AuthMethod::TempToken(RedactedString(
                 "test_temp_token".to_string()
             )))
@@ -420,10 +1085,295 @@ mod tests {
     fn test_deepgram_new_with_api_key() {
         let client = Deepgram::new("test_api_key").unwrap();
         assert_eq!(
-            client.auth,
+            client.inner.auth,
+            Some(AuthMethod::ApiKey(RedactedString(
+                "test_api_key".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_deepgram_new_rejects_empty_api_key() {
+        assert!(matches!(
+            Deepgram::new(""),
+            Err(DeepgramError::InvalidApiKey)
+        ));
+        assert!(matches!(
+            Deepgram::new(" \t\n"),
+            Err(DeepgramError::InvalidApiKey)
+        ));
+        assert!(matches!(
+            Deepgram::builder().api_key("  ").build(),
+            Err(DeepgramError::InvalidApiKey)
+        ));
+    }
+
+    #[test]
+    fn test_deepgram_new_trims_api_key() {
+        let client = Deepgram::new("  test_api_key\n").unwrap();
+        assert_eq!(
+            client.inner.auth,
             Some(AuthMethod::ApiKey(RedactedString(
                 "test_api_key".to_string()
             )))
         );
     }
+
+    #[tokio::test]
+    async fn custom_headers_preserve_authorization() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = crate::mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { crate::mock_server::json(200, "{}") }
+        })
+        .await;
+
+        let dg = Deepgram::builder()
+            .base_url(base_url.parse().unwrap())
+            .api_key("token")
+            .header(
+                HeaderName::from_static("x-tenant-id"),
+                HeaderValue::from_static("acme"),
+            )
+            .header(AUTHORIZATION, HeaderValue::from_static("Token stolen"))
+            .build()
+            .unwrap();
+        let _: serde_json::Value = send_and_translate_response(dg.inner.client.get(&base_url))
+            .await
+            .unwrap();
+
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(request.headers()["x-tenant-id"], "acme");
+        assert_eq!(request.headers()["authorization"], "Token token");
+    }
+
+    #[tokio::test]
+    async fn auth_scheme() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = crate::mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { crate::mock_server::json(200, "{}") }
+        })
+        .await;
+
+        for (scheme, expected) in [
+            (None, "Token key"),
+            (Some(AuthScheme::Token), "Token key"),
+            (Some(AuthScheme::Bearer), "Bearer key"),
+        ] {
+            let mut builder = Deepgram::builder()
+                .base_url(base_url.parse().unwrap())
+                .api_key("key");
+            if let Some(scheme) = scheme {
+                builder = builder.auth_scheme(scheme);
+            }
+            let dg = builder.build().unwrap();
+            let _: serde_json::Value = send_and_translate_response(dg.inner.client.get(&base_url))
+                .await
+                .unwrap();
+
+            let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+            assert_eq!(request.headers()["authorization"], expected, "{scheme:?}");
+        }
+
+        let dg = Deepgram::builder()
+            .temp_token("token")
+            .auth_scheme(AuthScheme::Token)
+            .build()
+            .unwrap();
+        assert_eq!(
+            dg.inner.auth.as_ref().unwrap().header_value(),
+            "Token token"
+        );
+    }
+
+    #[tokio::test]
+    async fn custom_client() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = crate::mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { crate::mock_server::json(200, "{}") }
+        })
+        .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-trace-id", HeaderValue::from_static("abc123"));
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .user_agent("acme-gateway/1.0")
+            .build()
+            .unwrap();
+
+        let dg = Deepgram::with_client("key", http.clone()).unwrap();
+        let _: serde_json::Value = send_and_translate_response(dg.inner.client.get(&base_url))
+            .await
+            .unwrap();
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(request.headers()["x-trace-id"], "abc123");
+        assert_eq!(request.headers()["authorization"], "Token key");
+        assert_eq!(request.headers()["user-agent"], "acme-gateway/1.0");
+
+        let dg = Deepgram::builder()
+            .api_key("key")
+            .user_agent("acme-voice/2.1")
+            .client(http)
+            .build()
+            .unwrap();
+        let _: serde_json::Value = send_and_translate_response(dg.inner.client.get(&base_url))
+            .await
+            .unwrap();
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(request.headers()["x-trace-id"], "abc123");
+        assert_eq!(request.headers()["user-agent"], "acme-voice/2.1");
+    }
+
+    #[tokio::test]
+    async fn user_agent() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = crate::mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { crate::mock_server::json(200, "{}") }
+        })
+        .await;
+
+        let default = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let custom = Deepgram::builder()
+            .base_url(base_url.parse().unwrap())
+            .user_agent("acme-voice/2.1")
+            .build()
+            .unwrap();
+        for dg in [default, custom] {
+            let _: serde_json::Value = send_and_translate_response(dg.inner.client.get(&base_url))
+                .await
+                .unwrap();
+        }
+
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(
+            request.headers()["user-agent"],
+            concat!("deepgram-rust-sdk/", env!("CARGO_PKG_VERSION"))
+        );
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(request.headers()["user-agent"], "acme-voice/2.1");
+    }
+
+    #[tokio::test]
+    async fn http_requests_use_proxy() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let proxy = crate::mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { crate::mock_server::json(200, "{}") }
+        })
+        .await;
+
+        let dg = Deepgram::builder()
+            .base_url("http://deepgram.invalid".parse().unwrap())
+            .proxy(proxy.parse().unwrap())
+            .build()
+            .unwrap();
+        let _: serde_json::Value =
+            send_and_translate_response(dg.inner.client.get("http://deepgram.invalid/v1/projects"))
+                .await
+                .unwrap();
+
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "http://deepgram.invalid/v1/projects"
+        );
+    }
+
+    #[tokio::test]
+    async fn api_error_status_and_body() {
+        let base_url = crate::mock_server::rest(|_| async {
+            crate::mock_server::json(
+                400,
+                r#"{
+                    "err_code": "Bad Request",
+                    "err_msg": "Bad Request: failed to process audio: corrupt or unsupported data",
+                    "request_id": "00000000-0000-4000-8000-000000000000"
+                }"#,
+            )
+        })
+        .await;
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let err = send_and_translate_response::<serde_json::Value>(dg.inner.client.get(&base_url))
+            .await
+            .unwrap_err();
+        let DeepgramError::DeepgramApiError { status, body } = err else {
+            panic!("expected an API error, got {err:?}");
+        };
+        assert_eq!(status, 400);
+        assert_eq!(body.err_code.as_deref(), Some("Bad Request"));
+        assert_eq!(
+            body.err_msg.as_deref(),
+            Some("Bad Request: failed to process audio: corrupt or unsupported data")
+        );
+        assert_eq!(
+            body.request_id.as_deref(),
+            Some("00000000-0000-4000-8000-000000000000")
+        );
+        assert!(body.raw.contains("corrupt or unsupported data"));
+    }
+
+    #[test]
+    fn api_error_body_not_json() {
+        let body = DeepgramApiErrorBody::parse("<html>Bad Gateway</html>".to_string());
+        assert_eq!(body.err_code, None);
+        assert_eq!(body.to_string(), "<html>Bad Gateway</html>");
+    }
+
+    #[tokio::test]
+    async fn clones_share_connection_pool() {
+        let (base_url, connections) =
+            crate::mock_server::keep_alive(|_| async { crate::mock_server::json(200, "{}") }).await;
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let clone = dg.clone();
+        assert!(Arc::ptr_eq(&dg.inner, &clone.inner));
+
+        for client in [&dg, &clone] {
+            let _: serde_json::Value =
+                send_and_translate_response(client.inner.client.get(&base_url))
+                    .await
+                    .unwrap();
+        }
+        assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn request_timeout() {
+        let base_url = crate::mock_server::rest(|_| async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            crate::mock_server::json(200, "{}")
+        })
+        .await;
+
+        let dg = Deepgram::builder()
+            .base_url(base_url.parse().unwrap())
+            .timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        let err = send_and_translate_response::<serde_json::Value>(dg.inner.client.get(&base_url))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeepgramError::Timeout(_)), "{err:?}");
+    }
+
+    #[test]
+    fn io_error_conversion() {
+        fn feed_audio() -> crate::Result<()> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "ffmpeg exited"))?;
+            Ok(())
+        }
+
+        let err = feed_audio().unwrap_err();
+        assert!(matches!(err, DeepgramError::IoError(_)), "{err:?}");
+        let source = std::error::Error::source(&err)
+            .and_then(|source| source.downcast_ref::<io::Error>())
+            .unwrap();
+        assert_eq!(source.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(source.to_string(), "ffmpeg exited");
+    }
 }
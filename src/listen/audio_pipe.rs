@@ -0,0 +1,240 @@
+//! Convert raw audio to the format sent to Deepgram.
+//!
+//! Requires the `audio` feature.
+
+use bytes::{Bytes, BytesMut};
+use dasp_interpolate::{linear::Linear, Interpolator};
+use futures::{Stream, StreamExt};
+
+use crate::{common::options::Encoding, DeepgramError, Result};
+
+/// Resamples and downmixes a stream of raw audio before it is sent.
+///
+/// The input is interleaved 16-bit little-endian PCM with any sample rate and
+/// number of channels. The output is mono PCM at the target sample rate,
+/// encoded as [`Encoding::Linear16`] (the default) or [`Encoding::Linear32`].
+/// Set the same encoding and sample rate on the
+/// [`WebsocketBuilder`](super::websocket::WebsocketBuilder), with one channel.
+///
+/// ```
+/// use bytes::Bytes;
+/// use deepgram::{common::options::Encoding, listen::audio_pipe::AudioPipe};
+/// use futures::StreamExt;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), deepgram::DeepgramError> {
+/// // 10 ms of 48 kHz stereo silence.
+/// let input = futures::stream::iter([Bytes::from(vec![0; 48 * 2 * 2 * 10])]);
+///
+/// let output: Vec<Bytes> = AudioPipe::new(48000, 2)
+///     .sample_rate(16000)
+///     .encoding(Encoding::Linear16)
+///     .pipe(input)?
+///     .collect()
+///     .await;
+/// assert_eq!(output.iter().map(Bytes::len).sum::<usize>(), 16 * 2 * 10);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioPipe {
+    input_sample_rate: u32,
+    input_channels: u16,
+    sample_rate: u32,
+    encoding: Encoding,
+}
+
+impl AudioPipe {
+    /// Convert audio recorded at `input_sample_rate` with `input_channels`
+    /// interleaved channels.
+    ///
+    /// Defaults to producing 16 kHz [`Encoding::Linear16`] audio.
+    pub fn new(input_sample_rate: u32, input_channels: u16) -> Self {
+        Self {
+            input_sample_rate,
+            input_channels,
+            sample_rate: 16000,
+            encoding: Encoding::Linear16,
+        }
+    }
+
+    /// Set the sample rate of the output.
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Set the encoding of the output, either [`Encoding::Linear16`] or
+    /// [`Encoding::Linear32`].
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Convert the audio produced by `stream`.
+    ///
+    /// Chunks may split samples and frames anywhere. Returns an error if the
+    /// encoding is not supported or a sample rate or channel count is zero.
+    pub fn pipe<S>(&self, stream: S) -> Result<impl Stream<Item = Bytes>>
+    where
+        S: Stream<Item = Bytes>,
+    {
+        let bytes_per_sample = match self.encoding {
+            Encoding::Linear16 => 2,
+            Encoding::Linear32 => 4,
+            _ => {
+                return Err(DeepgramError::InvalidOptions(format!(
+                    "cannot convert audio to {} encoding",
+                    self.encoding.as_str()
+                )))
+            }
+        };
+        if self.input_sample_rate == 0 || self.sample_rate == 0 || self.input_channels == 0 {
+            return Err(DeepgramError::InvalidOptions(
+                "sample rates and channels must not be zero".to_string(),
+            ));
+        }
+
+        let mut converter = Converter {
+            frame_size: 2 * usize::from(self.input_channels),
+            bytes_per_sample,
+            ratio: f64::from(self.input_sample_rate) / f64::from(self.sample_rate),
+            position: 0.0,
+            interpolator: Linear::new(0.0, 0.0),
+            leftover: Vec::new(),
+        };
+        Ok(stream
+            .map(move |chunk| converter.push(&chunk))
+            // An empty message would tell Deepgram the stream is over.
+            .filter(|chunk| futures::future::ready(!chunk.is_empty())))
+    }
+}
+
+/// The state carried between chunks of a single stream.
+struct Converter {
+    frame_size: usize,
+    bytes_per_sample: usize,
+    /// Input frames per output frame.
+    ratio: f64,
+    /// Position of the next output frame, in input frames after the
+    /// interpolator's left frame.
+    position: f64,
+    interpolator: Linear<f64>,
+    /// Bytes of an incomplete frame at the end of the last chunk.
+    leftover: Vec<u8>,
+}
+
+impl Converter {
+    fn push(&mut self, chunk: &[u8]) -> Bytes {
+        self.leftover.extend_from_slice(chunk);
+        let frames = self.leftover.len() / self.frame_size;
+        let mut output = BytesMut::with_capacity(
+            (frames as f64 / self.ratio).ceil() as usize * self.bytes_per_sample,
+        );
+
+        for frame in self.leftover.chunks_exact(self.frame_size) {
+            while self.position < 1.0 {
+                let sample = self
+                    .interpolator
+                    .interpolate(self.position)
+                    .clamp(-1.0, 1.0);
+                if self.bytes_per_sample == 2 {
+                    output
+                        .extend_from_slice(&((sample * f64::from(i16::MAX)) as i16).to_le_bytes());
+                } else {
+                    output
+                        .extend_from_slice(&((sample * f64::from(i32::MAX)) as i32).to_le_bytes());
+                }
+                self.position += self.ratio;
+            }
+            self.position -= 1.0;
+
+            // Downmix by averaging the channels.
+            let sum: f64 = frame
+                .chunks_exact(2)
+                .map(|sample| f64::from(i16::from_le_bytes([sample[0], sample[1]])))
+                .sum();
+            let channels = (self.frame_size / 2) as f64;
+            self.interpolator
+                .next_source_frame(sum / channels / f64::from(i16::MAX));
+        }
+
+        self.leftover.drain(..frames * self.frame_size);
+        output.freeze()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures::StreamExt;
+
+    use super::AudioPipe;
+    use crate::{common::options::Encoding, DeepgramError};
+
+    /// `frames` frames of 48 kHz stereo audio, a 1 kHz tone on the left
+    /// channel and silence on the right.
+    fn stereo_tone(frames: usize) -> Vec<u8> {
+        (0..frames)
+            .flat_map(|i| {
+                let t = i as f64 / 48000.0;
+                let left = ((t * 1000.0 * std::f64::consts::TAU).sin() * 16000.0) as i16;
+                [left.to_le_bytes(), 0i16.to_le_bytes()]
+            })
+            .flatten()
+            .collect()
+    }
+
+    async fn convert(pipe: &AudioPipe, chunks: Vec<Vec<u8>>) -> Vec<u8> {
+        let input = futures::stream::iter(chunks.into_iter().map(Bytes::from));
+        let output: Vec<Bytes> = pipe.pipe(input).unwrap().collect().await;
+        assert!(output.iter().all(|chunk| !chunk.is_empty()));
+        output.concat()
+    }
+
+    #[tokio::test]
+    async fn resamples_48k_stereo_to_16k_mono() {
+        let audio = stereo_tone(4800);
+        let pipe = AudioPipe::new(48000, 2);
+
+        let whole = convert(&pipe, vec![audio.clone()]).await;
+        // 0.1 seconds at 16 kHz, two bytes per frame.
+        assert_eq!(whole.len(), 1600 * 2);
+
+        // Splitting frames and samples across chunks does not change the output.
+        let split = convert(
+            &pipe,
+            audio.chunks(1001).map(<[u8]>::to_vec).collect::<Vec<_>>(),
+        )
+        .await;
+        assert_eq!(split, whole);
+
+        // The left channel is halved by the downmix.
+        let peak = whole
+            .chunks_exact(2)
+            .map(|sample| i16::from_le_bytes([sample[0], sample[1]]).abs())
+            .max()
+            .unwrap();
+        assert!((7500..=8000).contains(&peak), "{peak}");
+
+        let linear32 = convert(&pipe.clone().encoding(Encoding::Linear32), vec![audio]).await;
+        assert_eq!(linear32.len(), 1600 * 4);
+    }
+
+    #[test]
+    fn rejects_unsupported_output() {
+        let input = futures::stream::empty::<Bytes>();
+        assert!(matches!(
+            AudioPipe::new(48000, 2)
+                .encoding(Encoding::Mulaw)
+                .pipe(input),
+            Err(DeepgramError::InvalidOptions(_))
+        ));
+
+        let input = futures::stream::empty::<Bytes>();
+        assert!(matches!(
+            AudioPipe::new(48000, 0).pipe(input),
+            Err(DeepgramError::InvalidOptions(_))
+        ));
+    }
+}
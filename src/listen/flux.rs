@@ -16,7 +16,6 @@ use std::{
     time::Duration,
 };
 
-use anyhow::anyhow;
 use bytes::Bytes;
 use futures::{
     channel::mpsc::{self, Receiver, Sender},
@@ -26,6 +25,7 @@ use futures::{
 };
 use http::Request;
 use pin_project::pin_project;
+use serde_json::{Map, Value};
 use serde_urlencoded;
 use tokio::fs::File;
 use tokio_tungstenite::{tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream};
@@ -40,16 +40,15 @@ use uuid::Uuid;
 use self::file_chunker::FileChunker;
 use crate::{
     common::{
-        flux_response::FluxResponse,
+        flux_response::{FluxResponse, TurnEvent},
         options::{Encoding, Options},
     },
+    websocket::{add_custom_headers, connect, request_id},
     Deepgram, DeepgramError, Result, Transcription,
 };
 
 static FLUX_URL_PATH: &str = "transcription/v2/listen";
 
-static USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
-
 #[derive(Clone, Debug)]
 pub struct FluxBuilder<'a> {
     deepgram: &'a Deepgram,
@@ -57,6 +56,7 @@ pub struct FluxBuilder<'a> {
     encoding: Option<Encoding>,
     sample_rate: Option<u32>,
     stream_url: Url,
+    detect_sequence_gaps: bool,
 }
 
 impl Transcription<'_> {
@@ -73,7 +73,7 @@ impl Transcription<'_> {
     ///     common::options::{Encoding, Model, Options},
     /// };
     ///
-    /// let dg = Deepgram::new(std::env::var("DEEPGRAM_API_TOKEN").unwrap_or_default()).unwrap();
+    /// let dg = Deepgram::new("apikey12345").unwrap();
     /// let transcription = dg.transcription();
     /// let builder = transcription
     ///     .flux_request()
@@ -99,7 +99,7 @@ impl Transcription<'_> {
     ///     common::options::{Encoding, Model, Options},
     /// };
     ///
-    /// let dg = Deepgram::new(std::env::var("DEEPGRAM_API_TOKEN").unwrap_or_default()).unwrap();
+    /// let dg = Deepgram::new("apikey12345").unwrap();
     /// let transcription = dg.transcription();
     /// let options = Options::builder()
     ///     .model(Model::FluxGeneralEn)
@@ -116,12 +116,13 @@ impl Transcription<'_> {
             encoding: None,
             sample_rate: None,
             stream_url: self.flux_url(),
+            detect_sequence_gaps: false,
         }
     }
 
     fn flux_url(&self) -> Url {
         let mut url =
-            self.0.base_url.join(FLUX_URL_PATH).expect(
+            self.0.inner.base_url.join(FLUX_URL_PATH).expect(
                 "base_url is checked to be a valid base_url when constructing Deepgram client",
             );
 
@@ -152,31 +153,33 @@ impl FluxBuilder<'_> {
     fn as_url(&self) -> std::result::Result<Url, serde_urlencoded::ser::Error> {
         let Self {
             deepgram: _,
+            detect_sequence_gaps: _,
             options,
             encoding,
             sample_rate,
             stream_url,
         } = self;
 
-        let mut url = stream_url.clone();
-        {
-            let mut pairs = url.query_pairs_mut();
-
-            // Add standard options.
-            pairs.extend_pairs(
-                serde_urlencoded::from_str::<Vec<(String, String)>>(&options.urlencoded()?)
-                    .expect("constructed query string can be deserialized"),
-            );
-
-            // Add streaming-specific options
-            if let Some(encoding) = encoding {
-                pairs.append_pair("encoding", encoding.as_str());
-            }
-            if let Some(sample_rate) = sample_rate {
-                pairs.append_pair("sample_rate", &sample_rate.to_string());
-            }
+        // Add streaming-specific options
+        let mut streaming = Vec::new();
+        if let Some(encoding) = encoding {
+            streaming.push(("encoding", encoding.as_str().to_string()));
+        }
+        if let Some(sample_rate) = sample_rate {
+            streaming.push(("sample_rate", sample_rate.to_string()));
         }
 
+        let mut url = stream_url.clone();
+        url.query_pairs_mut()
+            // Add standard options, except those set on this builder.
+            .extend_pairs(
+                options
+                    .to_query_pairs()
+                    .into_iter()
+                    .filter(|(key, _)| !streaming.iter().any(|(set, _)| set == key)),
+            )
+            .extend_pairs(streaming);
+
         Ok(url)
     }
 
@@ -189,6 +192,17 @@ impl FluxBuilder<'_> {
         self.sample_rate = Some(sample_rate);
         self
     }
+
+    /// Yield a [`DeepgramError::SequenceGap`] before any message whose
+    /// `sequence_id` does not follow the highest one received so far.
+    ///
+    /// Deepgram numbers its messages consecutively, so a gap means messages
+    /// were lost, which helps spot unreliable connections. The message is
+    /// still yielded after the error. Disabled by default.
+    pub fn detect_sequence_gaps(mut self, detect: bool) -> Self {
+        self.detect_sequence_gaps = detect;
+        self
+    }
 }
 
 impl FluxBuilder<'_> {
@@ -252,7 +266,7 @@ impl FluxBuilder<'_> {
                     chunk = stream.next() => {
                         match chunk {
                             Some(Ok(audio)) => {
-                                if let Err(err) = handle.send_data(audio.to_vec()).await {
+                                if let Err(err) = handle.send_data(audio).await {
                                     if tx.send(Err(err)).await.is_err() {
                                         break;
                                     }
@@ -293,7 +307,7 @@ enum ControlMessage {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum WsMessage {
-    Audio(Vec<u8>),
+    Audio(Bytes),
     CloseStream,
 }
 
@@ -302,12 +316,18 @@ pub struct FluxHandle {
     message_tx: Sender<WsMessage>,
     pub(crate) response_rx: Receiver<Result<FluxResponse>>,
     request_id: Uuid,
+    effective_options: Option<Map<String, Value>>,
 }
 
 impl FluxHandle {
     async fn new(builder: FluxBuilder<'_>) -> Result<FluxHandle> {
+        builder.options.validate()?;
         let url = builder.as_url()?;
         let host = url.host_str().ok_or(DeepgramError::InvalidUrl)?;
+        let host = match url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
 
         let request = {
             let http_builder = Request::builder()
@@ -318,46 +338,52 @@ impl FluxHandle {
                 .header("connection", "upgrade")
                 .header("upgrade", "websocket")
                 .header("sec-websocket-version", "13")
-                .header("user-agent", USER_AGENT);
+                .header("user-agent", &builder.deepgram.inner.user_agent);
 
-            let builder = if let Some(auth) = &builder.deepgram.auth {
+            let request_builder = if let Some(auth) = &builder.deepgram.inner.auth {
                 http_builder.header("authorization", auth.header_value())
             } else {
                 http_builder
             };
-            builder.body(())?
+            let mut request = request_builder.body(())?;
+            add_custom_headers(request.headers_mut(), &builder.deepgram.inner.headers);
+            request
         };
 
-        let (ws_stream, upgrade_response) = tokio_tungstenite::connect_async(request).await?;
-
-        let request_id = upgrade_response
-            .headers()
-            .get("dg-request-id")
-            .ok_or(DeepgramError::UnexpectedServerResponse(anyhow!(
-                "Websocket upgrade headers missing request ID"
-            )))?
-            .to_str()
-            .ok()
-            .and_then(|req_header_str| Uuid::parse_str(req_header_str).ok())
-            .ok_or(DeepgramError::UnexpectedServerResponse(anyhow!(
-                "Received malformed request ID in websocket upgrade headers"
-            )))?;
+        let proxy = builder
+            .deepgram
+            .inner
+            .proxy
+            .as_ref()
+            .and_then(|proxy| proxy.resolve(&url));
+        let (ws_stream, upgrade_response) = connect(
+            request,
+            proxy.as_ref(),
+            None,
+            builder.deepgram.inner.connect_timeout,
+        )
+        .await?;
+
+        let request_id = request_id(&upgrade_response)?;
 
         let (message_tx, message_rx) = mpsc::channel(256);
         let (response_tx, response_rx) = mpsc::channel(256);
 
-        tokio::task::spawn(run_flux_worker(ws_stream, message_rx, response_tx));
+        let monitor = builder.detect_sequence_gaps.then(SequenceMonitor::default);
+        tokio::task::spawn(run_flux_worker(ws_stream, message_rx, response_tx, monitor));
 
         Ok(FluxHandle {
             message_tx,
             response_rx,
             request_id,
+            effective_options: None,
         })
     }
 
-    pub async fn send_data(&mut self, data: Vec<u8>) -> Result<()> {
+    /// Send a chunk of audio to the Deepgram API without copying it.
+    pub async fn send_data(&mut self, data: impl Into<Bytes>) -> Result<()> {
         self.message_tx
-            .send(WsMessage::Audio(data))
+            .send(WsMessage::Audio(data.into()))
             .await
             .map_err(|err| DeepgramError::InternalClientError(err.into()))?;
         Ok(())
@@ -375,21 +401,60 @@ impl FluxHandle {
         Ok(())
     }
 
-    #[allow(clippy::let_and_return)]
     pub async fn receive(&mut self) -> Option<Result<FluxResponse>> {
         let resp = self.response_rx.next().await;
+        if let Some(Ok(FluxResponse::Connected { params, .. })) = &resp {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(params = %serde_json::Value::Object(params.clone()), "connected");
+            if !params.is_empty() {
+                self.effective_options = Some(params.clone());
+            }
+        }
         resp
     }
 
+    /// Receive responses like [`FluxHandle::receive`], skipping turn updates.
+    ///
+    /// Only [`FluxResponse::TurnInfo`] messages for an
+    /// [`EndOfTurn`](TurnEvent::EndOfTurn) or
+    /// [`EagerEndOfTurn`](TurnEvent::EagerEndOfTurn) are yielded. Other
+    /// messages, such as fatal errors, and every error are passed through.
+    pub fn receive_final(&mut self) -> impl Stream<Item = Result<FluxResponse>> + '_ {
+        futures::stream::unfold(self, |handle| async move {
+            loop {
+                match handle.receive().await? {
+                    Ok(FluxResponse::TurnInfo { ref event, .. })
+                        if !matches!(event, TurnEvent::EndOfTurn | TurnEvent::EagerEndOfTurn) =>
+                    {
+                        continue
+                    }
+                    response => return Some((response, handle)),
+                }
+            }
+        })
+    }
+
     pub fn request_id(&self) -> Uuid {
         self.request_id
     }
+
+    /// The configuration Deepgram confirmed in its
+    /// [`Connected`](FluxResponse::Connected) message, beyond the request ID
+    /// and sequence ID.
+    ///
+    /// Comparing it with the requested options shows whether any of them were
+    /// ignored. Returns `None` until the message has been received, or if
+    /// Deepgram did not echo any parameters.
+    pub fn effective_options(&self) -> Option<&Map<String, Value>> {
+        self.effective_options.as_ref()
+    }
 }
 
 async fn run_flux_worker(
     ws_stream: WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>,
     mut message_rx: Receiver<WsMessage>,
     mut response_tx: Sender<Result<FluxResponse>>,
+    mut monitor: Option<SequenceMonitor>,
 ) -> Result<()> {
     // We use Vec<u8> for partial frames because we don't know if a fragment of a string is valid utf-8.
     let mut partial_frame: Vec<u8> = Vec::new();
@@ -401,19 +466,10 @@ async fn run_flux_worker(
             response = ws_stream_recv.next() => {
                 match response {
                     Some(Ok(Message::Text(response))) => {
-                        match serde_json::from_str(&response) {
-                            Ok(response) => {
-                                if (response_tx.send(Ok(response)).await).is_err() {
-                                    // Responses are no longer being received; close the stream.
-                                    break;
-                                }
-                            }
-                            Err(err) => {
-                                if (response_tx.send(Err(err.into())).await).is_err() {
-                                    // Responses are no longer being received; close the stream.
-                                    break;
-                                }
-                            }
+                        let response = crate::websocket::parse_message(&response);
+                        if !send_response(&mut response_tx, monitor.as_mut(), response).await {
+                            // Responses are no longer being received; close the stream.
+                            break;
                         }
                     }
                     Some(Ok(Message::Ping(value))) => {
@@ -434,12 +490,10 @@ async fn run_flux_worker(
                             OpCode::Data(Data::Text) => {
                                 partial_frame.extend(frame.payload());
                             }
-                            OpCode::Data(Data::Continue) => {
-                                // We know we're continuing a text frame because otherwise
-                                // partial_frame would be empty.
-                                if !partial_frame.is_empty() {
-                                    partial_frame.extend(frame.payload())
-                                }
+                            // We know we're continuing a text frame because otherwise
+                            // partial_frame would be empty.
+                            OpCode::Data(Data::Continue) if !partial_frame.is_empty() => {
+                                partial_frame.extend(frame.payload())
                             }
                             _ => {
                                 // Ignore other partial frames.
@@ -448,7 +502,7 @@ async fn run_flux_worker(
                         if frame.header().is_final {
                             let response = std::mem::take(&mut partial_frame);
                             let response = serde_json::from_slice(&response).map_err(|err| err.into());
-                            if (response_tx.send(response).await).is_err() {
+                            if !send_response(&mut response_tx, monitor.as_mut(), response).await {
                                 // Responses are no longer being received; close the stream.
                                 break
                             }
@@ -474,7 +528,7 @@ async fn run_flux_worker(
                 if is_open {
                     match message {
                         Some(WsMessage::Audio(audio)) => {
-                            if let Err(err) = ws_stream_send.send(Message::Binary(Bytes::from(audio))).await {
+                            if let Err(err) = ws_stream_send.send(Message::Binary(audio)).await {
                                 if response_tx.send(Err(err.into())).await.is_err() {
                                     break;
                                 }
@@ -513,6 +567,44 @@ async fn run_flux_worker(
     Ok(())
 }
 
+/// Tracks the `sequence_id` of received messages to detect gaps.
+#[derive(Debug, Default)]
+struct SequenceMonitor {
+    expected: Option<u32>,
+}
+
+impl SequenceMonitor {
+    fn check(&mut self, response: &FluxResponse) -> Option<DeepgramError> {
+        let got = response.sequence_id();
+        let gap = self
+            .expected
+            .filter(|&expected| expected != got)
+            .map(|expected| DeepgramError::SequenceGap { expected, got });
+        // A late message must not make the following ones look out of order.
+        let next = got.saturating_add(1);
+        self.expected = Some(self.expected.map_or(next, |expected| expected.max(next)));
+        gap
+    }
+}
+
+/// Send a response, preceded by the gap `monitor` detects before it, if any.
+///
+/// Returns `false` once responses are no longer being received.
+async fn send_response(
+    response_tx: &mut Sender<Result<FluxResponse>>,
+    monitor: Option<&mut SequenceMonitor>,
+    response: Result<FluxResponse>,
+) -> bool {
+    if let (Some(monitor), Ok(response)) = (monitor, &response) {
+        if let Some(gap) = monitor.check(response) {
+            if response_tx.send(Err(gap)).await.is_err() {
+                return false;
+            }
+        }
+    }
+    response_tx.send(response).await.is_ok()
+}
+
 #[derive(Debug)]
 #[pin_project]
 pub struct FluxStream {
@@ -606,14 +698,42 @@ mod file_chunker {
 
 #[cfg(test)]
 mod tests {
-    use crate::common::options::Options;
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::protocol::Message;
+
+    use super::Encoding;
+    use crate::{
+        common::{
+            flux_response::{FluxResponse, TurnEvent},
+            options::Options,
+        },
+        mock_server, DeepgramError,
+    };
+
+    fn turn_info(event: &str) -> String {
+        format!(
+            r#"{{
+                "type": "TurnInfo",
+                "request_id": "{}",
+                "sequence_id": 1,
+                "event": "{event}",
+                "turn_index": 0,
+                "audio_window_start": 0.0,
+                "audio_window_end": 1.5,
+                "transcript": "hello",
+                "words": [],
+                "end_of_turn_confidence": 0.9
+            }}"#,
+            mock_server::REQUEST_ID
+        )
+    }
 
     #[test]
     fn test_flux_url() {
         let dg = crate::Deepgram::new("token").unwrap();
         assert_eq!(
             dg.transcription().flux_url().to_string(),
-            "wss://api.deepgram.com/v2/listen",
+            "wss://api.deepgram.com/transcription/v2/listen",
         );
     }
 
@@ -623,7 +743,7 @@ mod tests {
             crate::Deepgram::with_base_url_and_api_key("http://localhost:8080", "token").unwrap();
         assert_eq!(
             dg.transcription().flux_url().to_string(),
-            "ws://localhost:8080/v2/listen",
+            "ws://localhost:8080/transcription/v2/listen",
         );
     }
 
@@ -637,4 +757,139 @@ mod tests {
         let builder = transcription.flux_request_with_options(opts.clone());
         assert_eq!(builder.urlencoded().unwrap(), opts.urlencoded().unwrap())
     }
+
+    #[test]
+    fn streaming_params_win_over_extra_params() {
+        let dg = crate::Deepgram::new("token").unwrap();
+        let opts = Options::builder()
+            .query_params([
+                ("sample_rate".to_string(), "8000".to_string()),
+                ("extra".to_string(), "kept".to_string()),
+            ])
+            .build();
+        let transcription = dg.transcription();
+        let builder = transcription
+            .flux_request_with_options(opts)
+            .encoding(Encoding::Linear16)
+            .sample_rate(16000);
+        assert_eq!(
+            builder.urlencoded().unwrap(),
+            "extra=kept&encoding=linear16&sample_rate=16000",
+        );
+    }
+
+    #[tokio::test]
+    async fn receive_final_yields_end_of_turn() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            for event in [
+                "StartOfTurn",
+                "Update",
+                "EagerEndOfTurn",
+                "TurnResumed",
+                "EndOfTurn",
+            ] {
+                ws.send(Message::text(turn_info(event))).await.unwrap();
+            }
+            ws.close(None).await.unwrap();
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().flux_request().handle().await.unwrap();
+        let events: Vec<_> = handle
+            .receive_final()
+            .map(|response| match response.unwrap() {
+                FluxResponse::TurnInfo { event, .. } => event,
+                response => panic!("unexpected response: {response:?}"),
+            })
+            .collect()
+            .await;
+
+        assert_eq!(events, [TurnEvent::EagerEndOfTurn, TurnEvent::EndOfTurn]);
+    }
+
+    #[tokio::test]
+    async fn effective_options() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            let connected = format!(
+                r#"{{"type": "Connected", "request_id": "{}", "sequence_id": 0, "model": "flux-general-en", "sample_rate": 16000}}"#,
+                mock_server::REQUEST_ID
+            );
+            ws.send(Message::text(connected)).await.unwrap();
+            ws.close(None).await.unwrap();
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().flux_request().handle().await.unwrap();
+        assert_eq!(handle.effective_options(), None);
+        assert!(matches!(
+            handle.receive().await,
+            Some(Ok(FluxResponse::Connected { .. }))
+        ));
+        let options = handle.effective_options().unwrap();
+        assert_eq!(options["model"], "flux-general-en");
+        assert_eq!(options["sample_rate"], 16000);
+        assert_eq!(options.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn effective_options_not_echoed() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            let connected = format!(
+                r#"{{"type": "Connected", "request_id": "{}", "sequence_id": 0}}"#,
+                mock_server::REQUEST_ID
+            );
+            ws.send(Message::text(connected)).await.unwrap();
+            ws.close(None).await.unwrap();
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().flux_request().handle().await.unwrap();
+        while handle.receive().await.is_some() {}
+        assert_eq!(handle.effective_options(), None);
+    }
+
+    #[tokio::test]
+    async fn detect_sequence_gaps() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            let connected = format!(
+                r#"{{"type": "Connected", "request_id": "{}", "sequence_id": 0}}"#,
+                mock_server::REQUEST_ID
+            );
+            ws.send(Message::text(connected)).await.unwrap();
+            for sequence_id in [1, 3, 2, 4] {
+                let message = turn_info("Update").replace(
+                    r#""sequence_id": 1"#,
+                    &format!(r#""sequence_id": {sequence_id}"#),
+                );
+                ws.send(Message::text(message)).await.unwrap();
+            }
+            ws.close(None).await.unwrap();
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg
+            .transcription()
+            .flux_request()
+            .detect_sequence_gaps(true)
+            .handle()
+            .await
+            .unwrap();
+        let mut received = Vec::new();
+        while let Some(response) = handle.receive().await {
+            received.push(match response {
+                Ok(response) => Ok(response.sequence_id()),
+                Err(DeepgramError::SequenceGap { expected, got }) => Err((expected, got)),
+                Err(err) => panic!("unexpected error: {err}"),
+            });
+        }
+
+        assert_eq!(
+            received,
+            [Ok(0), Ok(1), Err((2, 3)), Ok(3), Err((4, 2)), Ok(2), Ok(4)]
+        );
+    }
 }
@@ -1,5 +1,8 @@
 //! Listen module
 
+#[cfg(feature = "audio")]
+pub mod audio_pipe;
 pub mod flux;
+pub mod replay;
 pub mod rest;
 pub mod websocket;
@@ -0,0 +1,96 @@
+//! Replay live transcription sessions recorded as JSON lines.
+//!
+//! A session captured with one raw websocket message per line can be fed
+//! through the same code that consumes a live [`TranscriptionStream`].
+//!
+//! [`TranscriptionStream`]: super::websocket::TranscriptionStream
+
+use futures::Stream;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+use crate::{common::stream_response::StreamResponse, Result};
+
+/// Parse each line produced by `reader` into a [`StreamResponse`].
+///
+/// Blank lines are skipped. A line that is not a valid response yields a
+/// [`DeserializationError`] and the following lines are still read.
+/// Reading stops after the first I/O error, which is yielded as a
+/// [`IoError`].
+///
+/// [`DeserializationError`]: crate::DeepgramError::DeserializationError
+/// [`IoError`]: crate::DeepgramError::IoError
+///
+/// ```no_run
+/// use futures::StreamExt;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), deepgram::DeepgramError> {
+/// let file = tokio::fs::File::open("session.jsonl").await?;
+/// let responses = deepgram::listen::replay::from_jsonl(tokio::io::BufReader::new(file));
+/// let mut responses = std::pin::pin!(responses);
+///
+/// while let Some(response) = responses.next().await {
+///     println!("{:?}", response?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_jsonl<R>(reader: R) -> impl Stream<Item = Result<StreamResponse>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    futures::stream::unfold(Some(reader), |reader| async move {
+        let mut reader = reader?;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line).await {
+                Ok(0) => return None,
+                Ok(_) if line.trim().is_empty() => continue,
+                Ok(_) => {
+                    let response = crate::websocket::parse_message(line.trim_end());
+                    return Some((response, Some(reader)));
+                }
+                Err(err) => return Some((Err(err.into()), None)),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::from_jsonl;
+    use crate::{common::stream_response::StreamResponse, DeepgramError};
+
+    const SESSION: &str = r#"{"type": "SpeechStarted", "channel": [0, 1], "timestamp": 0.25}
+this is not json
+
+{"type": "UtteranceEnd", "channel": [0, 1], "last_word_end": 1.5}
+{"type": "Metadata", "request_id": "00000000-0000-4000-8000-000000000000", "created": "2025-01-01T00:00:00.000Z", "duration": 1.5, "channels": 1}
+"#;
+
+    #[tokio::test]
+    async fn replays_lines() {
+        let responses: Vec<_> = from_jsonl(SESSION.as_bytes()).collect().await;
+
+        assert_eq!(responses.len(), 4);
+        assert!(matches!(
+            responses[0],
+            Ok(StreamResponse::SpeechStartedResponse { timestamp, .. }) if timestamp == 0.25
+        ));
+        assert!(matches!(
+            &responses[1],
+            Err(DeepgramError::DeserializationError { raw, .. }) if raw == "this is not json"
+        ));
+        assert!(matches!(
+            responses[2],
+            Ok(StreamResponse::UtteranceEndResponse { last_word_end, .. }) if last_word_end == 1.5
+        ));
+        assert!(matches!(
+            responses[3],
+            Ok(StreamResponse::MetadataResponse { channels: 1, .. })
+        ));
+    }
+}
@@ -4,14 +4,21 @@
 //!
 //! [api]: https://developers.deepgram.com/api-reference/#transcription-prerecorded
 
-use reqwest::RequestBuilder;
+use std::{collections::VecDeque, time::Duration};
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use reqwest::{header::ACCEPT, Body, RequestBuilder};
 use url::Url;
 
 use crate::common::audio_source::AudioSource;
-use crate::{send_and_translate_response, Transcription};
+use crate::{
+    retry::{self, RetryPolicy},
+    translate_response, Deepgram, DeepgramError, Transcription,
+};
 
 use crate::common::batch_response::{CallbackResponse, Response};
-use crate::common::options::{Options, SerializableOptions};
+use crate::common::options::Options;
 
 static DEEPGRAM_API_URL_LISTEN: &str = "v1/listen";
 
@@ -65,9 +72,177 @@ impl Transcription<'_> {
         source: AudioSource,
         options: &Options,
     ) -> crate::Result<Response> {
-        let request_builder = self.make_prerecorded_request_builder(source, options);
+        self.prerecorded_request(source, options).send().await
+    }
 
-        send_and_translate_response(request_builder).await
+    /// Sends a request to Deepgram to transcribe pre-recorded audio hosted at `url`.
+    ///
+    /// Shorthand for [`Transcription::prerecorded`] with an [`AudioSource::from_url`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{common::options::Options, Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let url = "https://static.deepgram.com/examples/Bueller-Life-moves-pretty-fast.wav"
+    ///     .parse()
+    ///     .unwrap();
+    /// let response = dg_client
+    ///     .transcription()
+    ///     .prerecorded_url(url, &Options::default())
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prerecorded_url(&self, url: Url, options: &Options) -> crate::Result<Response> {
+        self.prerecorded(AudioSource::from_url(url), options).await
+    }
+
+    /// Sends a request to Deepgram to transcribe pre-recorded audio already held in memory.
+    ///
+    /// `mime_type` is sent as the `Content-Type` of the request, e.g. `audio/wav`
+    /// or a [`MimeType`](crate::common::audio_source::MimeType). It must not be empty.
+    /// Shorthand for [`Transcription::prerecorded`] with an
+    /// [`AudioSource::from_buffer_with_mime_type`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{common::options::Options, Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let audio = std::fs::read("Bueller-Life-moves-pretty-fast.wav").unwrap();
+    /// let response = dg_client
+    ///     .transcription()
+    ///     .prerecorded_buffer(audio, "audio/wav", &Options::default())
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prerecorded_buffer(
+        &self,
+        bytes: impl Into<Bytes>,
+        mime_type: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Response> {
+        let source = AudioSource::from_buffer_with_mime_type(bytes.into(), mime_type.as_ref());
+        self.prerecorded(source, options).await
+    }
+
+    /// Sends a request to Deepgram to transcribe pre-recorded audio read from a stream.
+    ///
+    /// The body is sent with chunked transfer encoding as the stream yields,
+    /// so the audio never has to be held in memory all at once. This makes it
+    /// possible to transcribe a file while it is still downloading.
+    ///
+    /// `mime_type` is sent as the `Content-Type` of the request, e.g. `audio/wav`
+    /// or a [`MimeType`](crate::common::audio_source::MimeType). It must not be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{common::options::Options, Deepgram, DeepgramError};
+    /// # use tokio::fs::File;
+    /// # use tokio_util::io::ReaderStream;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let file = File::open("Bueller-Life-moves-pretty-fast.wav").await.unwrap();
+    /// let response = dg_client
+    ///     .transcription()
+    ///     .prerecorded_stream(ReaderStream::new(file), "audio/wav", &Options::default())
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prerecorded_stream<S, E>(
+        &self,
+        stream: S,
+        mime_type: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Response>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+    {
+        let source =
+            AudioSource::from_buffer_with_mime_type(Body::wrap_stream(stream), mime_type.as_ref());
+        self.prerecorded(source, options).await
+    }
+
+    /// Sends a request to Deepgram to transcribe pre-recorded audio, yielding
+    /// the results as the response body arrives instead of once it is complete.
+    ///
+    /// When Deepgram sends the body as a sequence of JSON documents, each one
+    /// is yielded as soon as it has been received in full, so the partial
+    /// results for a long file can be used early. Otherwise the single
+    /// response is yielded once the body is complete, as with
+    /// [`Transcription::prerecorded`].
+    ///
+    /// A document that cannot be parsed is yielded as a
+    /// [`DeserializationError`](DeepgramError::DeserializationError) and ends
+    /// the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use deepgram::{common::{audio_source::AudioSource, options::Options}, Deepgram, DeepgramError};
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// let dg_client = Deepgram::from_env()?;
+    /// let source = AudioSource::from_url("https://example.com/long-recording.wav");
+    ///
+    /// let partials = dg_client
+    ///     .transcription()
+    ///     .prerecorded_partials(source, &Options::default())
+    ///     .await?;
+    /// let mut partials = std::pin::pin!(partials);
+    /// while let Some(response) = partials.next().await {
+    ///     println!("{}", response?.to_transcript());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails before yielding anything under the same conditions as
+    /// [`Transcription::prerecorded`].
+    pub async fn prerecorded_partials(
+        &self,
+        source: AudioSource,
+        options: &Options,
+    ) -> crate::Result<impl Stream<Item = crate::Result<Response>>> {
+        self.prerecorded_request(source, options).partials().await
     }
 
     /// Sends a request to Deepgram to transcribe pre-recorded audio using the Callback feature.
@@ -123,10 +298,55 @@ impl Transcription<'_> {
         options: &Options,
         callback: &str,
     ) -> crate::Result<CallbackResponse> {
-        let request_builder =
-            self.make_prerecorded_callback_request_builder(source, options, callback);
+        self.prerecorded_request(source, options)
+            .callback(callback)
+            .await
+    }
 
-        send_and_translate_response(request_builder).await
+    /// Construct a pre-recorded transcription request whose settings can be
+    /// overridden for this request only, such as its timeout or retry policy.
+    ///
+    /// Once configured, the request can be sent with any of
+    /// [`PrerecordedBuilder::send`], [`PrerecordedBuilder::partials`] or
+    /// [`PrerecordedBuilder::callback`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// #
+    /// # use deepgram::{common::{audio_source::AudioSource, options::Options}, Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// let dg_client = Deepgram::builder()
+    ///     .timeout(Duration::from_secs(30))
+    ///     .build()?;
+    /// let source = AudioSource::from_url("https://example.com/long-recording.wav");
+    ///
+    /// // This recording takes longer than most to transcribe.
+    /// let response = dg_client
+    ///     .transcription()
+    ///     .prerecorded_request(source, &Options::default())
+    ///     .timeout(Duration::from_secs(600))
+    ///     .send()
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prerecorded_request(
+        &self,
+        source: AudioSource,
+        options: &Options,
+    ) -> PrerecordedBuilder<'_> {
+        PrerecordedBuilder {
+            deepgram: self.0,
+            source,
+            options: options.clone(),
+            timeout: None,
+            retry: None,
+        }
     }
 
     /// Makes a [`reqwest::RequestBuilder`] without actually sending the request.
@@ -188,11 +408,14 @@ impl Transcription<'_> {
         source: AudioSource,
         options: &Options,
     ) -> RequestBuilder {
-        let request_builder = self
-            .0
-            .client
-            .post(self.listen_url())
-            .query(&SerializableOptions(options));
+        let mut query = options.to_query_pairs();
+        if source.is_wav() {
+            // The header describes the audio, which these parameters would mark as raw.
+            query.retain(|(key, _)| {
+                !matches!(key.as_str(), "encoding" | "sample_rate" | "channels")
+            });
+        }
+        let request_builder = self.0.inner.client.post(self.listen_url()).query(&query);
 
         source.fill_body(request_builder)
     }
@@ -260,18 +483,291 @@ impl Transcription<'_> {
         options: &Options,
         callback: &str,
     ) -> RequestBuilder {
+        // An invalid callback URL is left for Deepgram to reject.
+        let callback = options
+            .callback_url(callback)
+            .unwrap_or_else(|_| callback.to_string());
         self.make_prerecorded_request_builder(source, options)
             .query(&[("callback", callback)])
     }
 
     fn listen_url(&self) -> Url {
-        self.0.base_url.join(DEEPGRAM_API_URL_LISTEN).unwrap()
+        self.0.inner.base_url.join(DEEPGRAM_API_URL_LISTEN).unwrap()
+    }
+}
+
+/// A pre-recorded transcription request, with settings that take precedence
+/// over the client's for this request only.
+///
+/// Constructed using [`Transcription::prerecorded_request`].
+#[derive(Debug)]
+pub struct PrerecordedBuilder<'a> {
+    deepgram: &'a Deepgram,
+    source: AudioSource,
+    options: Options,
+    timeout: Option<Duration>,
+    retry: Option<RetryPolicy>,
+}
+
+impl PrerecordedBuilder<'_> {
+    /// Fail the request with [`DeepgramError::Timeout`] if it takes longer
+    /// than `timeout` to complete, instead of the
+    /// [`DeepgramBuilder::timeout`](crate::DeepgramBuilder::timeout) set on
+    /// the client.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry the request as allowed by `policy`, instead of the
+    /// [`DeepgramBuilder::retry`](crate::DeepgramBuilder::retry) policy set on
+    /// the client.
+    ///
+    /// Use a policy allowing no retries, such as `RetryPolicy::new(0,
+    /// Duration::ZERO)`, to send this request only once.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Send the request, as with [`Transcription::prerecorded`].
+    pub async fn send(self) -> crate::Result<Response> {
+        let response = self
+            .execute(|transcription, source, options| {
+                Ok(transcription.make_prerecorded_request_builder(source, options))
+            })
+            .await?;
+        translate_response(response).await
+    }
+
+    /// Send the request, yielding the results as the response body arrives,
+    /// as with [`Transcription::prerecorded_partials`].
+    pub async fn partials(self) -> crate::Result<impl Stream<Item = crate::Result<Response>>> {
+        let response = self
+            .execute(|transcription, source, options| {
+                Ok(transcription
+                    .make_prerecorded_request_builder(source, options)
+                    .header(ACCEPT, "application/json"))
+            })
+            .await?;
+        if !response.status().is_success() {
+            return Err(DeepgramError::from_response(response).await);
+        }
+        Ok(parse_documents(response.bytes_stream()))
+    }
+
+    /// Send the request using the Callback feature, as with
+    /// [`Transcription::prerecorded_callback`].
+    pub async fn callback(self, callback: &str) -> crate::Result<CallbackResponse> {
+        let response = self
+            .execute(|transcription, source, options| {
+                let callback = options.callback_url(callback)?;
+                Ok(transcription
+                    .make_prerecorded_callback_request_builder(source, options, &callback))
+            })
+            .await?;
+        translate_response(response).await
+    }
+
+    /// Validate the request, then send the one made by `build` with this
+    /// request's settings, falling back to the client's.
+    async fn execute(
+        self,
+        build: impl FnOnce(Transcription<'_>, AudioSource, &Options) -> crate::Result<RequestBuilder>,
+    ) -> crate::Result<reqwest::Response> {
+        self.source.validate()?;
+        self.options.validate()?;
+        let mut request_builder = build(self.deepgram.transcription(), self.source, &self.options)?;
+        if let Some(timeout) = self.timeout {
+            request_builder = request_builder.timeout(timeout);
+        }
+
+        let policy = self.retry.as_ref().or(self.deepgram.inner.retry.as_ref());
+        Ok(retry::send(request_builder, policy).await?)
+    }
+}
+
+/// Parse the JSON documents of a response body as each one is completed.
+fn parse_documents<B>(body: B) -> impl Stream<Item = crate::Result<Response>>
+where
+    B: Stream<Item = reqwest::Result<Bytes>>,
+{
+    struct State<B> {
+        body: B,
+        buffer: Vec<u8>,
+        scanner: DocumentScanner,
+        parsed: VecDeque<Response>,
+        error: Option<DeepgramError>,
+        done: bool,
+    }
+
+    let state = State {
+        body: Box::pin(body),
+        buffer: Vec::new(),
+        scanner: DocumentScanner::default(),
+        parsed: VecDeque::new(),
+        error: None,
+        done: false,
+    };
+    futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(response) = state.parsed.pop_front() {
+                return Some((Ok(response), state));
+            }
+            if let Some(err) = state.error.take() {
+                return Some((Err(err), state));
+            }
+            if state.done {
+                return None;
+            }
+
+            match state.body.next().await {
+                Some(Ok(chunk)) => state.buffer.extend_from_slice(&chunk),
+                Some(Err(err)) => {
+                    state.done = true;
+                    return Some((Err(err.into()), state));
+                }
+                None => {
+                    state.done = true;
+                    if state.buffer.iter().all(u8::is_ascii_whitespace) {
+                        return None;
+                    }
+                    // Only an incomplete document can be left over.
+                    return Some((parse_document(&state.buffer), state));
+                }
+            }
+
+            loop {
+                let end = match state.scanner.scan(&state.buffer) {
+                    Scanned::Complete(end) => end,
+                    // The rest can only be parsed as a whole, if at all.
+                    Scanned::Stray => state.buffer.len(),
+                    // Wait for the rest of the document.
+                    Scanned::Incomplete => break,
+                };
+                let response = parse_document(&state.buffer[..end]);
+                state.buffer.drain(..end);
+                match response {
+                    Ok(response) => state.parsed.push_back(response),
+                    Err(err) => {
+                        state.done = true;
+                        state.error = Some(err);
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn parse_document(document: &[u8]) -> crate::Result<Response> {
+    serde_json::from_slice(document).map_err(|source| DeepgramError::DeserializationError {
+        source,
+        raw: String::from_utf8_lossy(document).into_owned(),
+    })
+}
+
+/// Finds where each top-level JSON document of a growing buffer ends, by
+/// tracking the nesting of objects and arrays outside of strings, so each
+/// byte is only looked at once however the body is split.
+#[derive(Debug, Default)]
+struct DocumentScanner {
+    // How much of the buffer has been looked at.
+    scanned: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+enum Scanned {
+    /// A document ends at this offset. The caller drains the buffer up to it.
+    Complete(usize),
+    /// Something other than an object or an array is at the top level. The
+    /// caller drains the whole buffer.
+    Stray,
+    Incomplete,
+}
+
+impl DocumentScanner {
+    fn scan(&mut self, buffer: &[u8]) -> Scanned {
+        while let Some(&byte) = buffer.get(self.scanned) {
+            self.scanned += 1;
+            if self.in_string {
+                match byte {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' if self.depth > 0 => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        let end = self.scanned;
+                        self.scanned = 0;
+                        return Scanned::Complete(end);
+                    }
+                }
+                b'"' if self.depth > 0 => self.in_string = true,
+                _ if self.depth > 0 || byte.is_ascii_whitespace() => {}
+                _ => {
+                    self.scanned = 0;
+                    return Scanned::Stray;
+                }
+            }
+        }
+        Scanned::Incomplete
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Deepgram;
+    use std::time::Duration;
+
+    use crate::{
+        common::{
+            audio_source::AudioSource,
+            options::{CallbackMethod, Encoding, Options},
+        },
+        mock_server, Deepgram, DeepgramError,
+    };
+
+    /// A 44-byte WAV header followed by four silent 16-bit samples.
+    fn wav() -> Vec<u8> {
+        let mut wav = b"RIFF\x2c\x00\x00\x00WAVEfmt ".to_vec();
+        wav.extend_from_slice(&[
+            16, 0, 0, 0, 1, 0, 1, 0, 0x80, 0x3e, 0, 0, 0, 0x7d, 0, 0, 2, 0, 16, 0,
+        ]);
+        wav.extend_from_slice(b"data\x08\x00\x00\x00");
+        wav.extend_from_slice(&[0; 8]);
+        wav
+    }
+
+    const PRERECORDED_RESPONSE: &str = r#"{
+        "metadata": {
+            "request_id": "00000000-0000-4000-8000-000000000000",
+            "transaction_key": "deprecated",
+            "sha256": "6d3c8b7a",
+            "created": "2025-01-01T00:00:00.000Z",
+            "duration": 1.5,
+            "channels": 1
+        },
+        "results": {
+            "channels": [{
+                "alternatives": [{
+                    "transcript": "hello world",
+                    "confidence": 0.98,
+                    "words": [
+                        {"word": "hello", "start": 0.1, "end": 0.5, "confidence": 0.99},
+                        {"word": "world", "start": 0.6, "end": 1.0, "confidence": 0.97}
+                    ]
+                }]
+            }]
+        }
+    }"#;
 
     #[test]
     fn listen_url() {
@@ -290,4 +786,390 @@ mod tests {
             "http://localhost:8888/abc/v1/listen"
         );
     }
+
+    #[tokio::test]
+    async fn prerecorded_url() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { mock_server::json(200, PRERECORDED_RESPONSE) }
+        })
+        .await;
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let response = dg
+            .transcription()
+            .prerecorded_url(
+                "https://example.com/audio.wav".parse().unwrap(),
+                &Options::builder().punctuate(true).build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.results.channels[0].alternatives[0].transcript,
+            "hello world"
+        );
+
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(request.method(), "POST");
+        assert_eq!(request.uri(), "/v1/listen?punctuate=true");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(request.body()).unwrap(),
+            serde_json::json!({"url": "https://example.com/audio.wav"})
+        );
+    }
+
+    #[tokio::test]
+    async fn prerecorded_buffer() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { mock_server::json(200, PRERECORDED_RESPONSE) }
+        })
+        .await;
+
+        let wav = wav();
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let response = dg
+            .transcription()
+            .prerecorded_buffer(wav.clone(), "audio/wav", &Options::default())
+            .await
+            .unwrap();
+        let alternatives = &response.results.channels[0].alternatives;
+        assert_eq!(alternatives.len(), 1);
+        assert_eq!(alternatives[0].transcript, "hello world");
+        assert_eq!(alternatives[0].words.len(), 2);
+
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(request.method(), "POST");
+        assert_eq!(request.uri().path(), "/v1/listen");
+        assert_eq!(request.headers()["content-type"], "audio/wav");
+        assert_eq!(request.body(), &wav);
+    }
+
+    #[tokio::test]
+    async fn wav_detection() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { mock_server::json(200, PRERECORDED_RESPONSE) }
+        })
+        .await;
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let options = Options::builder()
+            .encoding(Encoding::Linear16)
+            .punctuate(true)
+            .query_params([
+                ("sample_rate".to_string(), "16000".to_string()),
+                ("channels".to_string(), "1".to_string()),
+            ])
+            .build();
+
+        dg.transcription()
+            .prerecorded(AudioSource::from_buffer(wav()), &options)
+            .await
+            .unwrap();
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(request.headers()["content-type"], "audio/wav");
+        assert_eq!(request.uri().query(), Some("punctuate=true"));
+
+        // Raw PCM keeps the explicit MIME type and the raw audio parameters.
+        let pcm = wav()[44..].to_vec();
+        dg.transcription()
+            .prerecorded(
+                AudioSource::from_buffer_with_mime_type(pcm, "audio/l16"),
+                &options,
+            )
+            .await
+            .unwrap();
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(request.headers()["content-type"], "audio/l16");
+        assert_eq!(
+            request.uri().query(),
+            Some("channels=1&encoding=linear16&punctuate=true&sample_rate=16000")
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_mime_type() {
+        let dg = Deepgram::new("token").unwrap();
+        let err = dg
+            .transcription()
+            .prerecorded_buffer(vec![0; 8], " ", &Options::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeepgramError::InvalidOptions(_)));
+    }
+
+    #[tokio::test]
+    async fn prerecorded_stream() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { mock_server::json(200, PRERECORDED_RESPONSE) }
+        })
+        .await;
+
+        let chunks = ["first ", "second ", "third"]
+            .map(|chunk| Ok::<_, std::io::Error>(bytes::Bytes::from_static(chunk.as_bytes())));
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let response = dg
+            .transcription()
+            .prerecorded_stream(
+                futures::stream::iter(chunks),
+                "audio/wav",
+                &Options::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.results.channels[0].alternatives[0].transcript,
+            "hello world"
+        );
+
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(request.uri().path(), "/v1/listen");
+        assert_eq!(request.headers()["transfer-encoding"], "chunked");
+        assert_eq!(request.headers()["content-type"], "audio/wav");
+        assert_eq!(request.body(), b"first second third");
+    }
+
+    #[tokio::test]
+    async fn prerecorded_callback() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async {
+                mock_server::json(
+                    200,
+                    r#"{"request_id": "00000000-0000-4000-8000-000000000000"}"#,
+                )
+            }
+        })
+        .await;
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let options = Options::builder()
+            .callback_method(CallbackMethod::PUT)
+            .build();
+        let response = dg
+            .transcription()
+            .prerecorded_callback(
+                AudioSource::from_url("https://example.com/audio.wav"),
+                &options,
+                "https://example.com/webhook",
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.request_id.to_string(),
+            "00000000-0000-4000-8000-000000000000"
+        );
+
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(
+            request.uri(),
+            "/v1/listen?callback_method=put&callback=https%3A%2F%2Fexample.com%2Fwebhook"
+        );
+    }
+
+    #[tokio::test]
+    async fn prerecorded_callback_basic_auth() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async {
+                mock_server::json(
+                    200,
+                    r#"{"request_id": "00000000-0000-4000-8000-000000000000"}"#,
+                )
+            }
+        })
+        .await;
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let options = Options::builder()
+            .callback_basic_auth("webhook", "hunter2")
+            .build();
+        dg.transcription()
+            .prerecorded_callback(
+                AudioSource::from_url("https://example.com/audio.wav"),
+                &options,
+                "https://example.com/webhook",
+            )
+            .await
+            .unwrap();
+
+        let request = futures::StreamExt::next(&mut request_rx).await.unwrap();
+        assert_eq!(
+            request.uri(),
+            "/v1/listen?callback=https%3A%2F%2Fwebhook%3Ahunter2%40example.com%2Fwebhook"
+        );
+
+        let err = dg
+            .transcription()
+            .prerecorded_callback(
+                AudioSource::from_url("https://example.com/audio.wav"),
+                &options,
+                "not a url",
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeepgramError::InvalidOptions(_)));
+    }
+
+    #[tokio::test]
+    async fn request_timeout() {
+        let base_url = mock_server::rest(|_| async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            mock_server::json(200, PRERECORDED_RESPONSE)
+        })
+        .await;
+        let source = || AudioSource::from_url("https://example.com/audio.wav");
+
+        // The request's timeout is shorter than the client's.
+        let dg = Deepgram::builder()
+            .base_url(base_url.parse().unwrap())
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+        let err = dg
+            .transcription()
+            .prerecorded_request(source(), &Options::default())
+            .timeout(Duration::from_millis(50))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeepgramError::Timeout(_)), "{err:?}");
+
+        // The request's timeout is longer than the client's.
+        let dg = Deepgram::builder()
+            .base_url(base_url.parse().unwrap())
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        dg.transcription()
+            .prerecorded_request(source(), &Options::default())
+            .timeout(Duration::from_secs(30))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn prerecorded_partials() {
+        use futures::StreamExt;
+
+        let (chunk_tx, chunk_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::chunked(chunk_rx).await;
+        let partial = PRERECORDED_RESPONSE.replace("hello world", "hello");
+        let (head, tail) = partial.split_at(partial.len() / 2);
+        chunk_tx.unbounded_send(head.into()).unwrap();
+        chunk_tx.unbounded_send(tail.into()).unwrap();
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let partials = dg
+            .transcription()
+            .prerecorded_partials(
+                AudioSource::from_url("https://example.com/audio.wav"),
+                &Options::default(),
+            )
+            .await
+            .unwrap();
+        let mut partials = std::pin::pin!(partials);
+
+        // The second partial is only sent once the first has been yielded.
+        let first = partials.next().await.unwrap().unwrap();
+        assert_eq!(
+            first.results.channels[0].alternatives[0].transcript,
+            "hello"
+        );
+        chunk_tx
+            .unbounded_send(format!("\n{PRERECORDED_RESPONSE}").into())
+            .unwrap();
+        drop(chunk_tx);
+
+        let second = partials.next().await.unwrap().unwrap();
+        assert_eq!(
+            second.results.channels[0].alternatives[0].transcript,
+            "hello world"
+        );
+        assert!(partials.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn parse_documents_in_small_chunks() {
+        use futures::StreamExt;
+
+        // Quotes, escapes and brackets within strings don't end the document.
+        let transcript = r#"a \"quoted\" \\ {brace} [bracket] "#.repeat(10_000);
+        let document = PRERECORDED_RESPONSE.replace("hello world", &transcript);
+        let body = format!("{document}\n{document}");
+        let chunks = body
+            .as_bytes()
+            .chunks(16)
+            .map(|chunk| Ok(bytes::Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>();
+
+        let responses: Vec<_> = super::parse_documents(futures::stream::iter(chunks))
+            .collect()
+            .await;
+        assert_eq!(responses.len(), 2);
+        for response in responses {
+            assert_eq!(
+                response.unwrap().results.channels[0].alternatives[0].transcript,
+                r#"a "quoted" \ {brace} [bracket] "#.repeat(10_000)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn parse_documents_stray_value() {
+        use futures::StreamExt;
+
+        let body = format!("{PRERECORDED_RESPONSE} 42");
+        let chunks = [Ok(bytes::Bytes::from(body))];
+
+        let responses: Vec<_> = super::parse_documents(futures::stream::iter(chunks))
+            .collect()
+            .await;
+        assert_eq!(responses.len(), 2);
+        assert!(responses[0].is_ok());
+        let Err(DeepgramError::DeserializationError { raw, .. }) = &responses[1] else {
+            panic!("expected a deserialization error, got {:?}", responses[1]);
+        };
+        assert_eq!(raw, " 42");
+    }
+
+    #[tokio::test]
+    async fn prerecorded_partials_truncated() {
+        use futures::StreamExt;
+
+        let base_url = mock_server::rest(|_| async {
+            mock_server::json(200, format!("{PRERECORDED_RESPONSE}{{\"metadata\""))
+        })
+        .await;
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let partials = dg
+            .transcription()
+            .prerecorded_partials(
+                AudioSource::from_url("https://example.com/audio.wav"),
+                &Options::default(),
+            )
+            .await
+            .unwrap();
+        let partials: Vec<_> = partials.collect().await;
+
+        assert_eq!(partials.len(), 2);
+        assert_eq!(
+            partials[0].as_ref().unwrap().results.channels[0].alternatives[0].transcript,
+            "hello world"
+        );
+        assert!(matches!(
+            partials[1],
+            Err(DeepgramError::DeserializationError { .. })
+        ));
+    }
 }
@@ -9,32 +9,43 @@
 //! [api]: https://developers.deepgram.com/api-reference/#transcription-streaming
 
 use std::{
+    collections::{HashMap, VecDeque},
     error::Error,
     fmt,
     ops::Deref,
     path::Path,
     pin::Pin,
-    task::{Context, Poll},
-    time::Duration,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
 };
 
-use anyhow::anyhow;
 use bytes::Bytes;
 use futures::{
-    channel::mpsc::{self, Receiver, Sender},
+    channel::{
+        mpsc::{self, Receiver, Sender},
+        oneshot,
+    },
     future::{pending, FutureExt},
     select_biased,
     stream::StreamExt,
-    SinkExt, Stream,
+    Sink, SinkExt, Stream,
 };
-use http::Request;
+use http::{HeaderMap, Request};
 use pin_project::pin_project;
 use serde_urlencoded;
-use tokio::fs::File;
+use tokio::{fs::File, io::AsyncRead, net::TcpStream};
 use tokio_tungstenite::{tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream};
+use tokio_util::io::ReaderStream;
 use tungstenite::{
     handshake::client,
-    protocol::frame::coding::{Data, OpCode},
+    protocol::{
+        frame::{
+            self,
+            coding::{CloseCode, Data, OpCode},
+        },
+        WebSocketConfig,
+    },
     Utf8Bytes,
 };
 use url::Url;
@@ -43,14 +54,21 @@ use uuid::Uuid;
 use self::file_chunker::FileChunker;
 use crate::{
     common::{
-        options::{Encoding, Endpointing, Options},
-        stream_response::StreamResponse,
+        options::{Encoding, Endpointing, OptionWarning, Options},
+        stream_response::{Metadata, ModelInfo, StreamResponse},
     },
-    Deepgram, DeepgramError, Result, Transcription,
+    websocket::{add_custom_headers, connect, request_id},
+    AuthMethod, Deepgram, DeepgramError, Result, Transcription,
 };
 
 static LIVE_LISTEN_URL_PATH: &str = "transcription/v1/listen";
 
+const DEFAULT_OUTBOUND_BUFFER: usize = 256;
+
+// Four times tungstenite's defaults, which long multichannel transcripts can exceed.
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 256 << 20;
+const DEFAULT_MAX_FRAME_SIZE: usize = 64 << 20;
+
 #[derive(Clone, Debug)]
 pub struct WebsocketBuilder<'a> {
     deepgram: &'a Deepgram,
@@ -66,6 +84,25 @@ pub struct WebsocketBuilder<'a> {
     stream_url: Url,
     keep_alive: Option<bool>,
     callback: Option<Url>,
+    reconnect: Option<ReconnectPolicy>,
+    outbound_buffer: Option<usize>,
+    idle_timeout: Option<Duration>,
+    on_raw_frame: Option<RawFrameCallback>,
+    max_message_size: Option<usize>,
+    max_frame_size: Option<usize>,
+}
+
+/// A callback observing every message received, as set with
+/// [`WebsocketBuilder::on_raw_frame`].
+#[derive(Clone)]
+struct RawFrameCallback(Arc<RawFrameFn>);
+
+type RawFrameFn = dyn Fn(&[u8]) + Send + Sync;
+
+impl fmt::Debug for RawFrameCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RawFrameCallback")
+    }
 }
 
 impl Transcription<'_> {
@@ -89,7 +126,7 @@ impl Transcription<'_> {
     ///     listen::websocket::WebsocketBuilder,
     /// };
     ///
-    /// let dg = Deepgram::new(std::env::var("DEEPGRAM_API_TOKEN").unwrap_or_default()).unwrap();
+    /// let dg = Deepgram::new("apikey12345").unwrap();
     /// let transcription = dg.transcription();
     /// let builder: WebsocketBuilder<'_> = transcription
     ///     .stream_request()
@@ -118,7 +155,7 @@ impl Transcription<'_> {
     ///     },
     /// };
     ///
-    /// let dg = Deepgram::new(std::env::var("DEEPGRAM_API_TOKEN").unwrap_or_default()).unwrap();
+    /// let dg = Deepgram::new("apikey12345").unwrap();
     /// let transcription = dg.transcription();
     /// let options = Options::builder()
     ///     .model(Model::Nova2)
@@ -130,7 +167,7 @@ impl Transcription<'_> {
     ///     )
     ///     .no_delay(true);
     ///
-    /// assert_eq!(&builder.urlencoded().unwrap(), "model=nova-2&detect_language=true&no_delay=true")
+    /// assert_eq!(&builder.urlencoded().unwrap(), "detect_language=true&model=nova-2&no_delay=true")
     /// ```
     pub fn stream_request_with_options(&self, options: Options) -> WebsocketBuilder<'_> {
         WebsocketBuilder {
@@ -147,13 +184,19 @@ impl Transcription<'_> {
             stream_url: self.listen_stream_url(),
             keep_alive: None,
             callback: None,
+            reconnect: None,
+            outbound_buffer: None,
+            idle_timeout: None,
+            on_raw_frame: None,
+            max_message_size: None,
+            max_frame_size: None,
         }
     }
 
     fn listen_stream_url(&self) -> Url {
         // base
         let mut url =
-            self.0.base_url.join(LIVE_LISTEN_URL_PATH).expect(
+            self.0.inner.base_url.join(LIVE_LISTEN_URL_PATH).expect(
                 "base_url is checked to be a valid base_url when constructing Deepgram client",
             );
 
@@ -184,7 +227,7 @@ impl WebsocketBuilder<'_> {
     ///     },
     /// };
     ///
-    /// let dg = Deepgram::new(std::env::var("DEEPGRAM_API_TOKEN").unwrap_or_default()).unwrap();
+    /// let dg = Deepgram::new("apikey12345").unwrap();
     /// let transcription = dg.transcription();
     /// let options = Options::builder()
     ///     .model(Model::Nova2)
@@ -196,7 +239,7 @@ impl WebsocketBuilder<'_> {
     ///     )
     ///     .no_delay(true);
     ///
-    /// assert_eq!(&builder.urlencoded().unwrap(), "model=nova-2&detect_language=true&no_delay=true")
+    /// assert_eq!(&builder.urlencoded().unwrap(), "detect_language=true&model=nova-2&no_delay=true")
     /// ```
     ///
     pub fn urlencoded(&self) -> std::result::Result<String, serde_urlencoded::ser::Error> {
@@ -208,6 +251,12 @@ impl WebsocketBuilder<'_> {
         let Self {
             deepgram: _,
             keep_alive: _,
+            reconnect: _,
+            outbound_buffer: _,
+            idle_timeout: _,
+            on_raw_frame: _,
+            max_message_size: _,
+            max_frame_size: _,
             options,
             encoding,
             sample_rate,
@@ -221,53 +270,108 @@ impl WebsocketBuilder<'_> {
             callback,
         } = self;
 
+        // Add streaming-specific options
+        let mut streaming = Vec::new();
+        if let Some(encoding) = encoding {
+            streaming.push(("encoding", encoding.as_str().to_string()));
+        }
+        if let Some(sample_rate) = sample_rate {
+            streaming.push(("sample_rate", sample_rate.to_string()));
+        }
+        if let Some(channels) = channels {
+            streaming.push(("channels", channels.to_string()));
+        }
+        if let Some(endpointing) = endpointing {
+            streaming.push(("endpointing", endpointing.to_string()));
+        }
+        if let Some(utterance_end_ms) = utterance_end_ms {
+            streaming.push(("utterance_end_ms", utterance_end_ms.to_string()));
+        }
+        if let Some(interim_results) = interim_results {
+            streaming.push(("interim_results", interim_results.to_string()));
+        }
+        if let Some(no_delay) = no_delay {
+            streaming.push(("no_delay", no_delay.to_string()));
+        }
+        if let Some(vad_events) = vad_events {
+            streaming.push(("vad_events", vad_events.to_string()));
+        }
+        if let Some(callback) = callback {
+            streaming.push(("callback", callback.to_string()));
+        }
+
         let mut url = stream_url.clone();
-        {
-            let mut pairs = url.query_pairs_mut();
-
-            // Add standard pre-recorded options.
-            //
-            // Here we serialize the options and then deserialize
-            // in order to avoid duplicating serialization logic.
-            //
-            // TODO: We should be able to lean on the serde more
-            // to avoid multiple serialization rounds.
-            pairs.extend_pairs(
-                serde_urlencoded::from_str::<Vec<(String, String)>>(&options.urlencoded()?)
-                    .expect("constructed query string can be deserialized"),
-            );
+        url.query_pairs_mut()
+            // Add standard pre-recorded options, except those set on this builder.
+            .extend_pairs(
+                options
+                    .to_query_pairs()
+                    .into_iter()
+                    .filter(|(key, _)| !streaming.iter().any(|(set, _)| set == key)),
+            )
+            .extend_pairs(streaming);
 
-            // Add streaming-specific options
-            if let Some(encoding) = encoding {
-                pairs.append_pair("encoding", encoding.as_str());
-            }
-            if let Some(sample_rate) = sample_rate {
-                pairs.append_pair("sample_rate", &sample_rate.to_string());
-            }
-            if let Some(channels) = channels {
-                pairs.append_pair("channels", &channels.to_string());
-            }
-            if let Some(endpointing) = endpointing {
-                pairs.append_pair("endpointing", &endpointing.to_string());
-            }
-            if let Some(utterance_end_ms) = utterance_end_ms {
-                pairs.append_pair("utterance_end_ms", &utterance_end_ms.to_string());
-            }
-            if let Some(interim_results) = interim_results {
-                pairs.append_pair("interim_results", &interim_results.to_string());
-            }
-            if let Some(no_delay) = no_delay {
-                pairs.append_pair("no_delay", &no_delay.to_string());
-            }
-            if let Some(vad_events) = vad_events {
-                pairs.append_pair("vad_events", &vad_events.to_string());
-            }
-            if let Some(callback) = callback {
-                pairs.append_pair("callback", callback.as_ref());
+        Ok(url)
+    }
+
+    /// Check the options for combinations Deepgram rejects before dialing.
+    fn validate(&self) -> Result<()> {
+        self.options.validate()?;
+
+        // Flux models only accept a subset of the streaming options, and are
+        // better served by `Transcription::flux_request`.
+        if let Some(model) = self.options.model().filter(|model| model.is_flux()) {
+            for (option, is_set) in [
+                ("interim_results", self.interim_results.is_some()),
+                ("endpointing", self.endpointing.is_some()),
+                ("keep_alive", self.keep_alive.is_some()),
+            ] {
+                if is_set {
+                    return Err(DeepgramError::InvalidOptions(format!(
+                        "`{option}` is not supported by the Flux model `{model}`"
+                    )));
+                }
             }
         }
 
-        Ok(url)
+        // Raw audio can only be decoded with both an encoding and a sample
+        // rate, and the sample rate is meaningless without an encoding.
+        let has_encoding = self.encoding.is_some() || self.options.encoding().is_some();
+        match (has_encoding, self.sample_rate) {
+            (true, None) => Err(DeepgramError::InvalidOptions(
+                "`sample_rate` is required when `encoding` is set".to_string(),
+            )),
+            (false, Some(_)) => Err(DeepgramError::InvalidOptions(
+                "`encoding` is required when `sample_rate` is set".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the options for combinations Deepgram accepts, but that are
+    /// probably mistakes, such as telephony `mulaw` audio at 44100 Hz.
+    ///
+    /// Connecting doesn't fail because of these. With the `tracing` feature,
+    /// they are logged as warnings when connecting.
+    ///
+    /// ```
+    /// # use deepgram::{common::options::{Encoding, OptionWarning}, Deepgram};
+    /// # let dg = Deepgram::new("token").unwrap();
+    /// let transcription = dg.transcription();
+    /// let builder = transcription
+    ///     .stream_request()
+    ///     .encoding(Encoding::Mulaw)
+    ///     .sample_rate(44100);
+    /// assert!(matches!(
+    ///     builder.warnings()[..],
+    ///     [OptionWarning::UnusualSampleRate { sample_rate: 44100, .. }]
+    /// ));
+    /// ```
+    pub fn warnings(&self) -> Vec<OptionWarning> {
+        let encoding = self.encoding.as_ref().or(self.options.encoding());
+        OptionWarning::check_sample_rate(encoding, self.sample_rate)
+            .into_iter()
+            .collect()
     }
 
     pub fn encoding(mut self, encoding: Encoding) -> Self {
@@ -282,6 +386,12 @@ impl WebsocketBuilder<'_> {
         self
     }
 
+    /// Set the number of interleaved channels in raw audio.
+    ///
+    /// This only describes the audio being sent, so Deepgram can deinterleave
+    /// it. Whether the channels are transcribed separately or mixed down is
+    /// controlled by [`OptionsBuilder::multichannel`](crate::common::options::OptionsBuilder::multichannel),
+    /// which needs `channels` to be set for multichannel raw audio.
     pub fn channels(mut self, channels: u16) -> Self {
         self.channels = Some(channels);
 
@@ -324,11 +434,84 @@ impl WebsocketBuilder<'_> {
         self
     }
 
+    /// Have Deepgram also deliver every result as a request to the
+    /// `callback` URL.
+    ///
+    /// Results still arrive over the websocket as usual; the callback
+    /// mirrors them, for instance to a backend that stores transcripts.
     pub fn callback(mut self, callback: Url) -> Self {
         self.callback = Some(callback);
 
         self
     }
+
+    /// Transparently re-dial with the same options if the connection drops.
+    ///
+    /// Audio that could not be sent while disconnected is retried on the new
+    /// connection, and a [`StreamResponse::Reconnected`] is yielded once it is
+    /// established.
+    pub fn reconnect(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = Some(policy);
+
+        self
+    }
+
+    /// Set how many messages may wait to be written to the websocket.
+    /// Defaults to 256.
+    ///
+    /// Once the queue is full, [`WebsocketHandle::send_data`] waits for the
+    /// connection to drain instead of buffering more audio in memory.
+    pub fn outbound_buffer(mut self, capacity: usize) -> Self {
+        self.outbound_buffer = Some(capacity);
+
+        self
+    }
+
+    /// Yield a [`StreamResponse::Idle`] once no audio has been sent and no
+    /// message received for `timeout`.
+    ///
+    /// Unlike an `UtteranceEnd`, which marks a pause in speech, this means
+    /// nothing is flowing at all, so the app can prompt the user or close
+    /// the stream. It is yielded once per idle period.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+
+        self
+    }
+
+    /// Call `callback` with the raw payload of every message received from
+    /// Deepgram, before it is parsed.
+    ///
+    /// Fragmented messages are passed once reassembled. This is meant for
+    /// debugging, for instance to record a session as JSON lines for
+    /// [`replay::from_jsonl`](crate::listen::replay::from_jsonl) or to
+    /// capture a message that fails to parse. The callback runs on the
+    /// task reading the connection, so it should return quickly.
+    pub fn on_raw_frame(mut self, callback: impl Fn(&[u8]) + Send + Sync + 'static) -> Self {
+        self.on_raw_frame = Some(RawFrameCallback(Arc::new(callback)));
+
+        self
+    }
+
+    /// Set the largest message that may be received from Deepgram, in bytes.
+    /// Defaults to 256 MiB.
+    ///
+    /// A larger message ends the stream with an error.
+    pub fn max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = Some(max_message_size);
+
+        self
+    }
+
+    /// Set the largest single websocket frame that may be received from
+    /// Deepgram, in bytes. Defaults to 64 MiB.
+    ///
+    /// A larger frame ends the stream with an error.
+    pub fn max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = Some(max_frame_size);
+
+        self
+    }
 }
 
 impl WebsocketBuilder<'_> {
@@ -365,7 +548,7 @@ impl WebsocketBuilder<'_> {
 
         let (tx, rx) = mpsc::channel(1);
         let mut is_done = false;
-        let request_id = handle.request_id();
+        let state = Arc::clone(&handle.state);
         tokio::task::spawn(async move {
             let mut handle = handle;
             let mut tx = tx;
@@ -377,13 +560,6 @@ impl WebsocketBuilder<'_> {
                     response = handle.response_rx.next() => {
                         // eprintln!("<stream> got response");
                         match response {
-                            Some(Ok(response)) if matches!(response, StreamResponse::TerminalResponse { .. }) => {
-                               // eprintln!( "<stream> got terminal response");
-                                if tx.send(Ok(response)).await.is_err() {
-                                    // Receiver has been dropped.
-                                    break;
-                                }
-                            }
                             Some(response) => {
                                 if tx.send(response).await.is_err() {
                                     // Receiver has been dropped.
@@ -401,7 +577,7 @@ impl WebsocketBuilder<'_> {
                     // Receiving audio data from stream.
                     chunk = stream.next() => {
                         match chunk {
-                            Some(Ok(audio)) => if let Err(err) = handle.send_data(audio.to_vec()).await {
+                            Some(Ok(audio)) => if let Err(err) = handle.send_data(audio).await {
                                 // eprintln!("<stream> got audio");
                                 if tx.send(Err(err)).await.is_err() {
                                     break;
@@ -424,7 +600,7 @@ impl WebsocketBuilder<'_> {
                                     }
                                 }
 
-                                if let Err(err) = handle.close_stream().await {
+                                if let Err(err) = handle.send_close_stream().await {
                                     if tx.send(Err(err)).await.is_err() {
                                         break;
                                     }
@@ -440,7 +616,7 @@ impl WebsocketBuilder<'_> {
         Ok(TranscriptionStream {
             rx,
             done: false,
-            request_id,
+            state,
         })
     }
 
@@ -450,32 +626,270 @@ impl WebsocketBuilder<'_> {
     }
 }
 
-macro_rules! send_message {
-    ($stream:expr, $response_tx:expr, $msg:expr) => {
-        if let Err(err) = $stream.send($msg).await {
-            if $response_tx.send(Err(err.into())).await.is_err() {
-                // Responses are no longer being received; close the stream.
-                break;
+/// Controls how a live transcription re-dials after its connection drops.
+///
+/// A reconnect is only attempted after a transport-level failure, never after
+/// the stream was closed with [`WebsocketHandle::close_stream`] or the server
+/// sent a close frame. Attempts are spaced by an exponential backoff starting
+/// at the initial backoff and capped at [`ReconnectPolicy::max_backoff`].
+///
+/// Used with [`WebsocketBuilder::reconnect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl ReconnectPolicy {
+    /// Try to reconnect up to `max_attempts` times, waiting `initial_backoff`
+    /// before the first attempt and doubling the wait after each failure.
+    pub fn new(max_attempts: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff,
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    /// Set the longest wait between two attempts. Defaults to 30 seconds.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self::new(5, Duration::from_millis(500))
+    }
+}
+
+/// Everything needed to open, and later reopen, the websocket connection.
+#[derive(Debug, Clone)]
+struct Connector {
+    url: Url,
+    auth: Option<AuthMethod>,
+    headers: HeaderMap,
+    proxy: Option<Url>,
+    user_agent: String,
+    config: WebSocketConfig,
+    connect_timeout: Option<Duration>,
+}
+
+impl Connector {
+    async fn connect(&self) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Uuid)> {
+        let host = self.url.host_str().ok_or(DeepgramError::InvalidUrl)?;
+        // Non-default ports are part of the Host header, which matters for self-hosted instances.
+        let host = match self.url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+
+        let request = {
+            let http_builder = Request::builder()
+                .method("GET")
+                .uri(self.url.to_string())
+                .header("sec-websocket-key", client::generate_key())
+                .header("host", host)
+                .header("connection", "upgrade")
+                .header("upgrade", "websocket")
+                .header("sec-websocket-version", "13")
+                .header("user-agent", &self.user_agent);
+
+            let builder = if let Some(auth) = &self.auth {
+                http_builder.header("authorization", auth.header_value())
+            } else {
+                http_builder
+            };
+            let mut request = builder.body(())?;
+            add_custom_headers(request.headers_mut(), &self.headers);
+            request
+        };
+
+        let (ws_stream, upgrade_response) = connect(
+            request,
+            self.proxy.as_ref(),
+            Some(self.config),
+            self.connect_timeout,
+        )
+        .await?;
+
+        let request_id = request_id(&upgrade_response)?;
+
+        Ok((ws_stream, request_id))
+    }
+
+    /// Keep trying to connect as allowed by `policy`, returning the new
+    /// connection and the attempt on which it succeeded.
+    async fn reconnect(
+        &self,
+        policy: &ReconnectPolicy,
+    ) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Uuid, u32)> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            tokio::time::sleep(policy.backoff(attempt)).await;
+            match self.connect().await {
+                Ok((ws_stream, request_id)) => return Ok((ws_stream, request_id, attempt)),
+                Err(err) if attempt >= policy.max_attempts => return Err(err),
+                Err(_) => {}
             }
         }
-    };
+    }
+}
+
+fn control_frame(message: &ControlMessage) -> Message {
+    Message::Text(Utf8Bytes::from(
+        serde_json::to_string(message).unwrap_or_default(),
+    ))
+}
+
+/// Counters describing the traffic of a live transcription.
+///
+/// See [`WebsocketHandle::metrics`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct StreamMetrics {
+    /// Bytes of audio written to the websocket.
+    pub bytes_sent: u64,
+
+    /// Messages received from Deepgram.
+    pub messages_received: u64,
+
+    /// Messages received from Deepgram, by their `type`, such as `Results`
+    /// or `Metadata`.
+    pub messages_by_type: HashMap<String, u64>,
+
+    /// The time between sending the first audio and receiving the first
+    /// transcription result.
+    pub time_to_first_transcript: Option<Duration>,
+}
+
+/// The close frame that ended a live transcription.
+///
+/// See [`WebsocketHandle::close_frame`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CloseFrame {
+    /// The websocket close code, such as `1000` for a normal closure or
+    /// `1011` for a server error.
+    pub code: u16,
+
+    /// The reason given along with the code, which may be empty.
+    pub reason: String,
+}
+
+impl CloseFrame {
+    fn normal() -> Self {
+        Self {
+            code: CloseCode::Normal.into(),
+            reason: String::new(),
+        }
+    }
+
+    /// Whether the connection was closed normally, with code `1000`.
+    pub fn is_normal(&self) -> bool {
+        CloseCode::from(self.code) == CloseCode::Normal
+    }
+
+    fn to_tungstenite(&self) -> frame::CloseFrame {
+        frame::CloseFrame {
+            code: self.code.into(),
+            reason: self.reason.as_str().into(),
+        }
+    }
+}
+
+/// What the worker learns about the stream, shared with the handle.
+#[derive(Debug, Default)]
+struct StreamState {
+    metrics: StreamMetrics,
+    first_audio_sent: Option<Instant>,
+    // From the first transcription result.
+    metadata: Option<Metadata>,
+    close_frame: Option<CloseFrame>,
+    // Set once the connection is gone, or while reconnecting.
+    disconnected: bool,
+    // Of the current connection, replaced on reconnect.
+    request_id: Uuid,
+}
+
+impl StreamState {
+    fn record_audio(&mut self, bytes: usize) {
+        self.metrics.bytes_sent += bytes as u64;
+        self.first_audio_sent.get_or_insert_with(Instant::now);
+    }
+
+    fn record_response(&mut self, response: &StreamResponse) {
+        self.metrics.messages_received += 1;
+        *self
+            .metrics
+            .messages_by_type
+            .entry(response_type(response).to_string())
+            .or_default() += 1;
+        if let StreamResponse::TranscriptResponse { metadata, .. } = response {
+            if let (None, Some(first_audio_sent)) =
+                (self.metrics.time_to_first_transcript, self.first_audio_sent)
+            {
+                self.metrics.time_to_first_transcript = Some(first_audio_sent.elapsed());
+            }
+            self.metadata.get_or_insert_with(|| metadata.clone());
+        }
+    }
 }
+
+fn lock(state: &Mutex<StreamState>) -> MutexGuard<'_, StreamState> {
+    // The state stays consistent even if a holder of the lock panicked.
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn run_worker(
-    ws_stream: WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>,
+    ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
     mut message_tx: Sender<WsMessage>,
     mut message_rx: Receiver<WsMessage>,
     mut response_tx: Sender<Result<StreamResponse>>,
     keep_alive: bool,
+    connector: Connector,
+    reconnect: Option<ReconnectPolicy>,
+    idle_timeout: Option<Duration>,
+    on_raw_frame: Option<RawFrameCallback>,
+    state: Arc<Mutex<StreamState>>,
 ) -> Result<()> {
+    let reconnect = reconnect.filter(|policy| policy.max_attempts > 0);
     // We use Vec<u8> for partial frames because we don't know if a fragment of a string is valid utf-8.
     let mut partial_frame: Vec<u8> = Vec::new();
     let (mut ws_stream_send, ws_stream_recv) = ws_stream.split();
     let mut ws_stream_recv = ws_stream_recv.fuse();
     let mut is_open: bool = true;
     let mut last_sent_message = tokio::time::Instant::now();
+    // Audio sent or a message received, for idle detection.
+    let mut last_activity = tokio::time::Instant::now();
+    let mut idle_reported = false;
+    // Messages that failed to send on a dropped connection, to be retried after reconnecting.
+    let mut unsent: VecDeque<Message> = VecDeque::new();
+    // Pings waiting for their pong, by payload.
+    let mut pings: VecDeque<(Bytes, oneshot::Sender<()>)> = VecDeque::new();
+    let mut next_ping: u64 = 0;
     loop {
         // eprintln!("<worker> loop");
         let sleep = tokio::time::sleep_until(last_sent_message + Duration::from_secs(3));
+        let idle = async move {
+            match idle_timeout {
+                Some(timeout) if is_open && !idle_reported => {
+                    tokio::time::sleep_until(last_activity + timeout).await
+                }
+                _ => pending::<()>().await,
+            }
+        };
+        let mut disconnected = false;
         // Primary event loop.
         select_biased! {
             _ = sleep.fuse() => {
@@ -487,24 +901,27 @@ async fn run_worker(
                     pending::<()>().await;
                 }
             }
+            _ = idle.fuse() => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("stream idle");
+                idle_reported = true;
+                let idle = StreamResponse::Idle { duration: last_activity.elapsed() };
+                if (response_tx.send(Ok(idle)).await).is_err() {
+                    // Responses are no longer being received; close the stream.
+                    break;
+                }
+            }
             response = ws_stream_recv.next() => {
+                if matches!(response, Some(Ok(Message::Text(_) | Message::Frame(_)))) {
+                    last_activity = tokio::time::Instant::now();
+                    idle_reported = false;
+                }
+                // A complete text message, whether sent whole or fragmented.
+                let mut text = None;
                 match response {
                     Some(Ok(Message::Text(response))) => {
                         // eprintln!("<worker> received dg response");
-                        match serde_json::from_str(&response) {
-                            Ok(response) => {
-                                if (response_tx.send(Ok(response)).await).is_err() {
-                                    // Responses are no longer being received; close the stream.
-                                    break;
-                                }
-                            }
-                            Err(err) =>{
-                                if (response_tx.send(Err(err.into())).await).is_err() {
-                                    // Responses are no longer being received; close the stream.
-                                    break;
-                                }
-                            }
-                        }
+                        text = Some(response);
                     }
                     Some(Ok(Message::Ping(value))) => {
                         // We don't really care if the server receives the pong.
@@ -512,14 +929,34 @@ async fn run_worker(
                     }
                     Some(Ok(Message::Close(None))) => {
                         // eprintln!("<worker> received websocket close");
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!("websocket closed by server");
+                        lock(&state).disconnected = true;
                         return Ok(());
                     }
                     Some(Ok(Message::Close(Some(closeframe)))) => {
                         // eprintln!("<worker> received websocket close");
-                        return Err(DeepgramError::WebsocketClose {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(code = u16::from(closeframe.code), reason = %closeframe.reason, "websocket closed by server");
+                        let frame = CloseFrame {
                             code: closeframe.code.into(),
                             reason: closeframe.reason.to_string(),
-                        });
+                        };
+                        {
+                            let mut state = lock(&state);
+                            state.close_frame = Some(frame.clone());
+                            state.disconnected = true;
+                        }
+                        if frame.is_normal() {
+                            return Ok(());
+                        }
+                        let close_error = || DeepgramError::WebsocketClose {
+                            code: frame.code,
+                            reason: frame.reason.clone(),
+                        };
+                        // Surface the error as the last response, in case anyone is still listening.
+                        let _ = response_tx.send(Err(close_error())).await;
+                        return Err(close_error());
                     }
 
                     Some(Ok(Message::Frame(frame))) => {
@@ -528,12 +965,10 @@ async fn run_worker(
                             OpCode::Data(Data::Text) => {
                                 partial_frame.extend(frame.payload());
                             }
-                            OpCode::Data(Data::Continue) => {
-                                // We know we're continuing a text frame because otherwise
-                                // partial_frame would be empty.
-                                if !partial_frame.is_empty() {
-                                    partial_frame.extend(frame.payload())
-                                }
+                            // We know we're continuing a text frame because otherwise
+                            // partial_frame would be empty.
+                            OpCode::Data(Data::Continue) if !partial_frame.is_empty() => {
+                                partial_frame.extend(frame.payload())
                             }
                             _ => {
                                 // Ignore other partial frames.
@@ -541,18 +976,27 @@ async fn run_worker(
                         }
                         if frame.header().is_final {
                             let response = std::mem::take(&mut partial_frame);
-                            let response = serde_json::from_slice(&response).map_err(|err| err.into());
-                            if (response_tx.send(response).await).is_err() {
-                                // Responses are no longer being received; close the stream.
-                                break
+                            text = Some(Utf8Bytes::from(String::from_utf8_lossy(&response).into_owned()));
+                        }
+                    }
+                    Some(Ok(Message::Pong(payload))) => {
+                        if let Some(index) = pings.iter().position(|(ping, _)| *ping == payload) {
+                            if let Some((_, pong_tx)) = pings.remove(index) {
+                                let _ = pong_tx.send(());
                             }
                         }
                     }
-                    Some(Ok(Message::Binary(_) | Message::Pong(_))) => {
-                        // We don't expect binary messages or pongs from the API.
+                    Some(Ok(Message::Binary(_))) => {
+                        // We don't expect binary messages from the API.
                         // They can be safely ignored.
                     }
 
+                    Some(Err(_)) | None if reconnect.is_some() && is_open => {
+                        // The connection dropped without a close handshake.
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!("connection dropped");
+                        disconnected = true;
+                    }
                     Some(Err(err)) => {
                         if (response_tx.send(Err(err.into())).await).is_err() {
                             // Responses are no longer being received; close the stream.
@@ -563,67 +1007,202 @@ async fn run_worker(
                     None => {
                         // Upstream is closed
                         // eprintln!("<worker> received None");
+                        lock(&state).disconnected = true;
                         return Ok(())
                     }
                 }
+                if let Some(response) = text {
+                    if let Some(RawFrameCallback(callback)) = &on_raw_frame {
+                        callback(response.as_bytes());
+                    }
+                    match crate::websocket::parse_message::<StreamResponse>(&response) {
+                        Ok(response) => {
+                            lock(&state).record_response(&response);
+                            #[cfg(feature = "tracing")]
+                            tracing::debug!(message_type = response_type(&response), "received message");
+                            let is_terminal = response.is_metadata();
+                            if (response_tx.send(Ok(response)).await).is_err() {
+                                // Responses are no longer being received; close the stream.
+                                break;
+                            }
+                            if is_terminal && !is_open {
+                                // The server has flushed everything after CloseStream;
+                                // close the socket cleanly rather than dropping it.
+                                let frame = CloseFrame::normal();
+                                let _ = ws_stream_send.send(Message::Close(Some(frame.to_tungstenite()))).await;
+                                let _ = ws_stream_send.close().await;
+                                lock(&state).close_frame = Some(frame);
+                                break;
+                            }
+                        }
+                        Err(err) =>{
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!(error = %err, "failed to parse message");
+                            if (response_tx.send(Err(err)).await).is_err() {
+                                // Responses are no longer being received; close the stream.
+                                break;
+                            }
+                        }
+                    }
+                }
             }
             message = message_rx.next() => {
                 // eprintln!("<worker> received message: {message:?}, {is_open:?}");
                 if is_open {
-                    match message {
-                        Some(WsMessage::Audio(audio))=> {
-                            send_message!(ws_stream_send, response_tx, Message::Binary(Bytes::from(audio.0)));
-                            last_sent_message = tokio::time::Instant::now();
-
-                        }
+                    let (message, closes_stream) = match message {
+                        Some(WsMessage::Audio(audio)) => (Message::Binary(audio.0), false),
                         Some(WsMessage::ControlMessage(msg)) => {
-                            send_message!(ws_stream_send, response_tx, Message::Text(
-                                Utf8Bytes::from(serde_json::to_string(&msg).unwrap_or_default())
-                            ));
-                            last_sent_message = tokio::time::Instant::now();
-                            if msg == ControlMessage::CloseStream {
-                                is_open = false;
+                            (control_frame(&msg), msg == ControlMessage::CloseStream)
+                        }
+                        Some(WsMessage::Ping(pong_tx)) => {
+                            let payload = Bytes::copy_from_slice(&next_ping.to_be_bytes());
+                            next_ping = next_ping.wrapping_add(1);
+                            pings.push_back((payload.clone(), pong_tx));
+                            (Message::Ping(payload), false)
+                        }
+                        // Input stream is shut down.  Keep processing responses.
+                        None => (control_frame(&ControlMessage::CloseStream), true),
+                    };
+                    #[cfg(feature = "tracing")]
+                    if let Message::Text(message) = &message {
+                        tracing::debug!(%message, "sending control message");
+                    }
+                    match ws_stream_send.send(message.clone()).await {
+                        Ok(()) => {
+                            if let Message::Binary(audio) = &message {
+                                last_activity = tokio::time::Instant::now();
+                                idle_reported = false;
+                                lock(&state).record_audio(audio.len());
+                                #[cfg(feature = "tracing")]
+                                tracing::trace!(bytes = audio.len(), bytes_sent = lock(&state).metrics.bytes_sent, "sent audio");
                             }
                         }
-                        None => {
-                            // Input stream is shut down.  Keep processing responses.
-                            send_message!(ws_stream_send, response_tx, Message::Text(
-                                Utf8Bytes::from(serde_json::to_string(&ControlMessage::CloseStream).unwrap_or_default())
-                            ));
-                            is_open = false;
+                        Err(_) if reconnect.is_some() && !closes_stream => {
+                            unsent.push_back(message);
+                            disconnected = true;
+                        }
+                        Err(err) => {
+                            if response_tx.send(Err(err.into())).await.is_err() {
+                                // Responses are no longer being received; close the stream.
+                                break;
+                            }
                         }
                     }
+                    last_sent_message = tokio::time::Instant::now();
+                    if closes_stream {
+                        is_open = false;
+                    }
                 }
             }
         };
+
+        if let (true, Some(policy)) = (disconnected, &reconnect) {
+            lock(&state).disconnected = true;
+            // The pings were lost with the connection.
+            pings.clear();
+            match connector.reconnect(policy).await {
+                Ok((ws_stream, request_id, attempt)) => {
+                    #[cfg(feature = "tracing")]
+                    {
+                        tracing::Span::current()
+                            .record("request_id", tracing::field::display(request_id));
+                        tracing::info!(attempt, "reconnected");
+                    }
+                    let (send, recv) = ws_stream.split();
+                    ws_stream_send = send;
+                    ws_stream_recv = recv.fuse();
+                    partial_frame.clear();
+                    {
+                        let mut state = lock(&state);
+                        state.disconnected = false;
+                        state.request_id = request_id;
+                    }
+                    if (response_tx
+                        .send(Ok(StreamResponse::Reconnected {
+                            attempt,
+                            request_id,
+                        }))
+                        .await)
+                        .is_err()
+                    {
+                        break;
+                    }
+                    while let Some(message) = unsent.pop_front() {
+                        let bytes = match &message {
+                            Message::Binary(audio) => audio.len(),
+                            _ => 0,
+                        };
+                        match ws_stream_send.send(message).await {
+                            Ok(()) if bytes > 0 => lock(&state).record_audio(bytes),
+                            Ok(()) => {}
+                            Err(err) => {
+                                let _ = response_tx.send(Err(err.into())).await;
+                            }
+                        }
+                    }
+                    last_sent_message = tokio::time::Instant::now();
+                }
+                Err(err) => {
+                    // Out of attempts; there is no connection left to close.
+                    #[cfg(feature = "tracing")]
+                    tracing::error!(error = %err, "failed to reconnect");
+                    is_open = false;
+                    let _ = response_tx.send(Err(err)).await;
+                    break;
+                }
+            }
+        }
     }
     // eprintln!("<worker> post loop");
-    if let Err(err) = ws_stream_send
-        .send(Message::Text(Utf8Bytes::from(
-            serde_json::to_string(&ControlMessage::CloseStream).unwrap_or_default(),
-        )))
-        .await
-    {
-        // If the response channel is closed, there's nothing to be done about it now.
-        let _ = response_tx.send(Err(err.into())).await;
+    lock(&state).disconnected = true;
+    if is_open {
+        if let Err(err) = ws_stream_send
+            .send(control_frame(&ControlMessage::CloseStream))
+            .await
+        {
+            // If the response channel is closed, there's nothing to be done about it now.
+            let _ = response_tx.send(Err(err.into())).await;
+        }
     }
     response_tx.close_channel();
+    // Our own sender would otherwise keep the channel below open forever.
+    drop(message_tx);
     // Waiting for message_tx to be dropped before exiting
     while message_rx.next().await.is_some() {
         // Receiving messages after closing down. Ignore them.
     }
     // eprintln!("<worker> exit");
+    #[cfg(feature = "tracing")]
+    tracing::debug!("stream closed");
     Ok(())
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// The `type` of a response, as sent by Deepgram.
+fn response_type(response: &StreamResponse) -> &str {
+    match response {
+        StreamResponse::TranscriptResponse { type_field, .. }
+        | StreamResponse::MetadataResponse { type_field, .. }
+        | StreamResponse::SpeechStartedResponse { type_field, .. }
+        | StreamResponse::UtteranceEndResponse { type_field, .. } => type_field,
+        #[allow(deprecated)]
+        StreamResponse::TerminalResponse { .. } => "Metadata",
+        StreamResponse::Warning { .. } => "Warning",
+        StreamResponse::Reconnected { .. } => "Reconnected",
+        StreamResponse::Idle { .. } => "Idle",
+        StreamResponse::Unknown(value) => value["type"].as_str().unwrap_or("Unknown"),
+    }
+}
+
+#[derive(Debug)]
 enum WsMessage {
     Audio(Audio),
     ControlMessage(ControlMessage),
+    /// A websocket ping, answered on the sender once its pong arrives.
+    Ping(oneshot::Sender<()>),
 }
 
 #[derive(Clone, PartialEq, Eq)]
-struct Audio(Vec<u8>);
+struct Audio(Bytes);
 
 impl fmt::Debug for Audio {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -631,7 +1210,7 @@ impl fmt::Debug for Audio {
             .field(&format!(
                 "<{} bytes (sha256:{})>",
                 self.0.len(),
-                &sha256::digest(&self.0)[..12]
+                &sha256::digest(&self.0[..])[..12]
             ))
             .finish()
     }
@@ -649,79 +1228,91 @@ impl Deref for Audio {
 pub struct WebsocketHandle {
     message_tx: Sender<WsMessage>,
     response_rx: Receiver<Result<StreamResponse>>,
-    request_id: Uuid,
+    // Responses read while waiting in `close_stream_and_wait`, not yet handed to `receive`.
+    pending: VecDeque<Result<StreamResponse>>,
+    state: Arc<Mutex<StreamState>>,
 }
 
 impl WebsocketHandle {
     async fn new(builder: WebsocketBuilder<'_>) -> Result<WebsocketHandle> {
+        builder.validate()?;
+        #[cfg(feature = "tracing")]
+        for warning in builder.warnings() {
+            tracing::warn!(%warning, "unusual options");
+        }
         let url = builder.as_url()?;
-        let host = url.host_str().ok_or(DeepgramError::InvalidUrl)?;
-
-        // Use the same user agent as the main HTTP client
-        static USER_AGENT: &str = concat!(
-            env!("CARGO_PKG_NAME"),
-            "/",
-            env!("CARGO_PKG_VERSION"),
-            " rust",
-        );
-
-        let request = {
-            let http_builder = Request::builder()
-                .method("GET")
-                .uri(url.to_string())
-                .header("sec-websocket-key", client::generate_key())
-                .header("host", host)
-                .header("connection", "upgrade")
-                .header("upgrade", "websocket")
-                .header("sec-websocket-version", "13")
-                .header("user-agent", USER_AGENT);
-
-            let builder = if let Some(auth) = &builder.deepgram.auth {
-                http_builder.header("authorization", auth.header_value())
-            } else {
-                http_builder
-            };
-            builder.body(())?
+        let connector = Connector {
+            proxy: builder
+                .deepgram
+                .inner
+                .proxy
+                .as_ref()
+                .and_then(|proxy| proxy.resolve(&url)),
+            url,
+            auth: builder.deepgram.inner.auth.clone(),
+            headers: builder.deepgram.inner.headers.clone(),
+            user_agent: builder.deepgram.inner.user_agent.clone(),
+            connect_timeout: builder.deepgram.inner.connect_timeout,
+            config: WebSocketConfig::default()
+                .max_message_size(Some(
+                    builder.max_message_size.unwrap_or(DEFAULT_MAX_MESSAGE_SIZE),
+                ))
+                .max_frame_size(Some(
+                    builder.max_frame_size.unwrap_or(DEFAULT_MAX_FRAME_SIZE),
+                )),
         };
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("deepgram_listen", request_id = tracing::field::Empty);
+        let (ws_stream, request_id) = connector.connect().await?;
+        #[cfg(feature = "tracing")]
+        {
+            span.record("request_id", tracing::field::display(request_id));
+            tracing::debug!(parent: &span, url = %connector.url, "connected");
+        }
 
-        let (ws_stream, upgrade_response) = tokio_tungstenite::connect_async(request).await?;
-
-        let request_id = upgrade_response
-            .headers()
-            .get("dg-request-id")
-            .ok_or(DeepgramError::UnexpectedServerResponse(anyhow!(
-                "Websocket upgrade headers missing request ID"
-            )))?
-            .to_str()
-            .ok()
-            .and_then(|req_header_str| Uuid::parse_str(req_header_str).ok())
-            .ok_or(DeepgramError::UnexpectedServerResponse(anyhow!(
-                "Received malformed request ID in websocket upgrade headers"
-            )))?;
-
-        let (message_tx, message_rx) = mpsc::channel(256);
+        let (message_tx, message_rx) =
+            mpsc::channel(builder.outbound_buffer.unwrap_or(DEFAULT_OUTBOUND_BUFFER));
         let (response_tx, response_rx) = mpsc::channel(256);
-
-        tokio::task::spawn({
-            let message_tx = message_tx.clone();
-            run_worker(
-                ws_stream,
-                message_tx,
-                message_rx,
-                response_tx,
-                builder.keep_alive.unwrap_or(false),
-            )
-        });
+        let state = Arc::new(Mutex::new(StreamState {
+            request_id,
+            ..StreamState::default()
+        }));
+
+        let worker = run_worker(
+            ws_stream,
+            message_tx.clone(),
+            message_rx,
+            response_tx,
+            builder.keep_alive.unwrap_or(false),
+            connector,
+            builder.reconnect,
+            builder.idle_timeout,
+            builder.on_raw_frame,
+            Arc::clone(&state),
+        );
+        #[cfg(feature = "tracing")]
+        let worker = tracing::Instrument::instrument(worker, span);
+        tokio::task::spawn(worker);
 
         Ok(WebsocketHandle {
             message_tx,
             response_rx,
-            request_id,
+            pending: VecDeque::new(),
+            state,
         })
     }
 
-    pub async fn send_data(&mut self, data: Vec<u8>) -> Result<()> {
-        let audio = Audio(data);
+    /// Send a chunk of audio to the Deepgram API.
+    ///
+    /// Each call sends exactly one binary websocket message: chunks are never
+    /// split or coalesced, so containers and codecs that need message
+    /// boundaries, such as Opus packets, can be sent one frame per call.
+    ///
+    /// Accepts anything convertible into [`Bytes`]. Passing `Bytes` (for example
+    /// one frozen out of a `BytesMut` buffer) or a `Vec<u8>` hands over the
+    /// allocation without copying the audio.
+    pub async fn send_data(&mut self, data: impl Into<Bytes>) -> Result<()> {
+        let audio = Audio(data.into());
         // eprintln!("<handle> sending audio: {audio:?}");
 
         self.message_tx
@@ -731,20 +1322,82 @@ impl WebsocketHandle {
         Ok(())
     }
 
-    /// Send a Finalize message to the Deepgram API to force the server to process
-    /// all the audio it has already received.
-    pub async fn finalize(&mut self) -> Result<()> {
-        self.send_control_message(ControlMessage::Finalize).await
+    /// Send several frames of audio, each as its own binary websocket message.
+    ///
+    /// Equivalent to calling [`WebsocketHandle::send_data`] for each frame,
+    /// in order. Stops at the first frame that cannot be sent.
+    pub async fn send_frames(&mut self, frames: impl IntoIterator<Item = Bytes>) -> Result<()> {
+        for frame in frames {
+            self.send_data(frame).await?;
+        }
+        Ok(())
     }
 
-    /// Send a KeepAlive message to the Deepgram API to ensure the connection
+    /// Send all audio produced by `reader` until it reaches end of file.
+    ///
+    /// The reader is only polled as fast as the connection accepts audio, so
+    /// this can be pointed directly at a file or a child process' stdout.
+    /// The stream is left open; call [`WebsocketHandle::close_stream`] once
+    /// no more audio will be sent.
+    pub async fn pipe_from<R: AsyncRead + Unpin>(&mut self, reader: R) -> Result<()> {
+        let mut chunks = ReaderStream::new(reader);
+        while let Some(chunk) = chunks.next().await {
+            self.send_data(chunk?).await?;
+        }
+        Ok(())
+    }
+
+    /// Send a Finalize message to the Deepgram API to force the server to process
+    /// all the audio it has already received.
+    ///
+    /// The results for that audio are sent as final, with `from_finalize`
+    /// set on the last one (see [`StreamResponse::is_finalize_ack`]), even if
+    /// the speaker has not paused. Unlike [`WebsocketHandle::close_stream`],
+    /// the stream stays open, so more audio can be sent afterwards. Deepgram
+    /// has no separate flush message for live transcription; this is the way
+    /// to get results for buffered audio without closing the stream.
+    pub async fn finalize(&mut self) -> Result<()> {
+        self.send_control_message(ControlMessage::Finalize).await
+    }
+
+    /// Send a KeepAlive message to the Deepgram API to ensure the connection
     /// isn't closed due to long idle times.
     pub async fn keep_alive(&mut self) -> Result<()> {
         self.send_control_message(ControlMessage::KeepAlive).await
     }
 
     /// Close the websocket stream. No more data should be sent after this is called.
+    ///
+    /// The remaining responses, up to the final
+    /// [`StreamResponse::MetadataResponse`], can still be read with
+    /// [`WebsocketHandle::receive`]. Use [`WebsocketHandle::close_stream_and_wait`]
+    /// to wait for that metadata instead.
     pub async fn close_stream(&mut self) -> Result<()> {
+        self.send_close_stream().await
+    }
+
+    /// Close the websocket stream and wait for Deepgram to finish processing.
+    ///
+    /// Sends a `CloseStream` message, then waits for the
+    /// [`StreamResponse::MetadataResponse`] carrying the final request metadata,
+    /// after which the websocket is closed cleanly. Transcription results that
+    /// arrive in the meantime are kept and can still be read with
+    /// [`WebsocketHandle::receive`].
+    ///
+    /// Returns `None` if the connection ended without a terminal response.
+    /// No more data should be sent after this is called.
+    pub async fn close_stream_and_wait(&mut self) -> Result<Option<StreamResponse>> {
+        self.send_close_stream().await?;
+        while let Some(response) = self.response_rx.next().await {
+            match response {
+                Ok(response) if response.is_metadata() => return Ok(Some(response)),
+                response => self.pending.push_back(response),
+            }
+        }
+        Ok(None)
+    }
+
+    async fn send_close_stream(&mut self) -> Result<()> {
         if !self.message_tx.is_closed() {
             self.send_control_message(ControlMessage::CloseStream)
                 .await?;
@@ -768,13 +1421,154 @@ impl WebsocketHandle {
 
     #[allow(clippy::let_and_return)]
     pub async fn receive(&mut self) -> Option<Result<StreamResponse>> {
+        if let Some(resp) = self.pending.pop_front() {
+            return Some(resp);
+        }
         let resp = self.response_rx.next().await;
         // eprintln!("<handle> receiving response: {resp:?}");
         resp
     }
 
+    /// Receive the next response like [`WebsocketHandle::receive`], giving
+    /// up if none arrives within `timeout`.
+    ///
+    /// Returns `Ok(None)` once the stream is closed, and a
+    /// [`DeepgramError::ReceiveTimeout`] if the time runs out. Deepgram keeps
+    /// sending results while audio flows, even during silence, so a timeout
+    /// points at a stalled connection. The handle can still be used after a
+    /// timeout.
+    pub async fn receive_timeout(&mut self, timeout: Duration) -> Result<Option<StreamResponse>> {
+        match tokio::time::timeout(timeout, self.receive()).await {
+            Ok(response) => response.transpose(),
+            Err(_) => Err(DeepgramError::ReceiveTimeout(timeout)),
+        }
+    }
+
+    /// Receive responses like [`WebsocketHandle::receive`], skipping interim
+    /// transcripts.
+    ///
+    /// Only [`StreamResponse::TranscriptResponse`]s with `is_final` set are
+    /// yielded. Every other response, and every error, is passed through.
+    pub fn receive_final(&mut self) -> impl Stream<Item = Result<StreamResponse>> + '_ {
+        futures::stream::unfold(self, |handle| async move {
+            loop {
+                match handle.receive().await? {
+                    Ok(StreamResponse::TranscriptResponse {
+                        is_final: false, ..
+                    }) => continue,
+                    response => return Some((response, handle)),
+                }
+            }
+        })
+    }
+
+    /// Whether the websocket connection is still open.
+    ///
+    /// Turns `false` once either side has closed it or it has dropped, and
+    /// while [reconnecting](WebsocketBuilder::reconnect).
+    pub fn is_connected(&self) -> bool {
+        !lock(&self.state).disconnected
+    }
+
+    /// Send a websocket ping and wait for Deepgram's pong, returning the
+    /// round-trip time.
+    ///
+    /// This checks the health of the connection without sending any audio,
+    /// for instance to keep a pool of pre-warmed connections. The pong is
+    /// handled in the background, but responses not yet received take up
+    /// buffer space meanwhile, so keep calling [`WebsocketHandle::receive`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the stream has been closed, or if the connection is lost
+    /// before the pong arrives.
+    pub async fn ping(&mut self) -> Result<Duration> {
+        let (pong_tx, pong_rx) = oneshot::channel();
+        let sent = Instant::now();
+        self.message_tx
+            .send(WsMessage::Ping(pong_tx))
+            .await
+            .map_err(|err| DeepgramError::InternalClientError(err.into()))?;
+        pong_rx
+            .await
+            .map_err(|err| DeepgramError::InternalClientError(err.into()))?;
+        Ok(sent.elapsed())
+    }
+
+    /// Returns a snapshot of the traffic seen so far on this stream.
+    ///
+    /// The counters carry over a [reconnect](WebsocketBuilder::reconnect).
+    pub fn metrics(&self) -> StreamMetrics {
+        lock(&self.state).metrics.clone()
+    }
+
+    /// Returns the close frame that ended the connection, if it has been
+    /// closed with one.
+    ///
+    /// Once [`WebsocketHandle::receive`] returns `None`, this tells a clean
+    /// shutdown, with a [normal](CloseFrame::is_normal) close code, from an
+    /// error reported by Deepgram, such as `1011`. It stays `None` if the
+    /// connection dropped without a close frame. Closing the stream with
+    /// [`WebsocketHandle::close_stream`] records a normal close.
+    ///
+    /// A close frame with an error code is also yielded by
+    /// [`WebsocketHandle::receive`] as a [`DeepgramError::WebsocketClose`].
+    pub fn close_frame(&self) -> Option<CloseFrame> {
+        lock(&self.state).close_frame.clone()
+    }
+
+    /// Returns the model that served this stream, once the first
+    /// transcription result has been received.
+    pub fn model_info(&self) -> Option<ModelInfo> {
+        lock(&self.state)
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.model_info.clone())
+    }
+
+    /// Returns the UUID of the model that served this stream, once the first
+    /// transcription result has been received.
+    pub fn model_uuid(&self) -> Option<String> {
+        lock(&self.state)
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.model_uuid.clone())
+    }
+
+    /// Split the handle into an [`AudioSink`] for sending audio and a
+    /// [`ResponseStream`] for receiving responses.
+    ///
+    /// The halves are independent, so audio can be sent from one task while
+    /// responses are received in another, without `select!`. Dropping or
+    /// closing the sink closes the stream like
+    /// [`WebsocketHandle::close_stream`], and the remaining responses are
+    /// still yielded.
+    pub fn split(self) -> (AudioSink, ResponseStream) {
+        let Self {
+            message_tx,
+            response_rx,
+            pending,
+            state,
+        } = self;
+        (
+            AudioSink { message_tx },
+            ResponseStream {
+                response_rx,
+                pending,
+                state,
+            },
+        )
+    }
+
+    /// Returns the Deepgram request ID for the speech-to-text live request.
+    ///
+    /// This is read from the websocket upgrade response, so it is available
+    /// as soon as the handle is created, before any response arrives. It is
+    /// the nil UUID if the server did not send a request ID.
+    ///
+    /// After a reconnect, this is the request ID of the new connection.
     pub fn request_id(&self) -> Uuid {
-        self.request_id
+        lock(&self.state).request_id
     }
 }
 
@@ -786,13 +1580,169 @@ enum ControlMessage {
     CloseStream,
 }
 
+/// Yields the same responses as [`WebsocketHandle::receive`], so that the
+/// [`StreamExt`] combinators can be used on them.
+impl Stream for WebsocketHandle {
+    type Item = Result<StreamResponse>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if let Some(response) = this.pending.pop_front() {
+            return Poll::Ready(Some(response));
+        }
+        this.response_rx.poll_next_unpin(cx)
+    }
+}
+
+/// Sends each chunk of audio like [`WebsocketHandle::send_data`].
+///
+/// Closing the sink sends a `CloseStream` message, after which the
+/// remaining responses can still be received. Together with the [`Stream`]
+/// implementation, this lets [`StreamExt::split`] separate sending audio
+/// from receiving responses.
+impl Sink<Bytes> for WebsocketHandle {
+    type Error = DeepgramError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        poll_ready_audio(&mut self.get_mut().message_tx, cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, audio: Bytes) -> Result<()> {
+        start_send_audio(&mut self.get_mut().message_tx, audio)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        poll_flush_audio(&mut self.get_mut().message_tx, cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        poll_close_audio(&mut self.get_mut().message_tx, cx)
+    }
+}
+
+fn poll_ready_audio(message_tx: &mut Sender<WsMessage>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+    message_tx
+        .poll_ready(cx)
+        .map_err(|err| DeepgramError::InternalClientError(err.into()))
+}
+
+fn start_send_audio(message_tx: &mut Sender<WsMessage>, audio: Bytes) -> Result<()> {
+    message_tx
+        .start_send(WsMessage::Audio(Audio(audio)))
+        .map_err(|err| DeepgramError::InternalClientError(err.into()))
+}
+
+fn poll_flush_audio(message_tx: &mut Sender<WsMessage>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+    Pin::new(message_tx)
+        .poll_flush(cx)
+        .map_err(|err| DeepgramError::InternalClientError(err.into()))
+}
+
+fn poll_close_audio(message_tx: &mut Sender<WsMessage>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+    if message_tx.is_closed() {
+        return Poll::Ready(Ok(()));
+    }
+    ready!(poll_ready_audio(message_tx, cx))?;
+    message_tx
+        .start_send(WsMessage::ControlMessage(ControlMessage::CloseStream))
+        .map_err(|err| DeepgramError::InternalClientError(err.into()))?;
+    message_tx.close_channel();
+    Poll::Ready(Ok(()))
+}
+
+/// The audio half of a [`WebsocketHandle`], as returned by
+/// [`WebsocketHandle::split`].
+///
+/// Each chunk of audio sent is written as its own binary websocket message.
+/// Closing or dropping the sink sends a `CloseStream` message, so Deepgram
+/// finishes processing and the [`ResponseStream`] ends.
+#[derive(Debug)]
+pub struct AudioSink {
+    message_tx: Sender<WsMessage>,
+}
+
+impl Sink<Bytes> for AudioSink {
+    type Error = DeepgramError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        poll_ready_audio(&mut self.get_mut().message_tx, cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, audio: Bytes) -> Result<()> {
+        start_send_audio(&mut self.get_mut().message_tx, audio)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        poll_flush_audio(&mut self.get_mut().message_tx, cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        poll_close_audio(&mut self.get_mut().message_tx, cx)
+    }
+}
+
+impl Drop for AudioSink {
+    fn drop(&mut self) {
+        // The worker sends a CloseStream message once the channel is closed
+        // and the audio already queued has been written.
+        self.message_tx.close_channel();
+    }
+}
+
+/// The response half of a [`WebsocketHandle`], as returned by
+/// [`WebsocketHandle::split`].
+///
+/// Yields the same responses as [`WebsocketHandle::receive`].
+#[derive(Debug)]
+pub struct ResponseStream {
+    response_rx: Receiver<Result<StreamResponse>>,
+    pending: VecDeque<Result<StreamResponse>>,
+    state: Arc<Mutex<StreamState>>,
+}
+
+impl ResponseStream {
+    /// Returns the Deepgram request ID for the speech-to-text live request.
+    ///
+    /// See [`WebsocketHandle::request_id`].
+    pub fn request_id(&self) -> Uuid {
+        lock(&self.state).request_id
+    }
+
+    /// Returns the traffic counters of the stream so far.
+    ///
+    /// See [`WebsocketHandle::metrics`].
+    pub fn metrics(&self) -> StreamMetrics {
+        lock(&self.state).metrics.clone()
+    }
+
+    /// Returns the close frame that ended the connection, if it has been
+    /// closed with one.
+    ///
+    /// See [`WebsocketHandle::close_frame`].
+    pub fn close_frame(&self) -> Option<CloseFrame> {
+        lock(&self.state).close_frame.clone()
+    }
+}
+
+impl Stream for ResponseStream {
+    type Item = Result<StreamResponse>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if let Some(response) = this.pending.pop_front() {
+            return Poll::Ready(Some(response));
+        }
+        this.response_rx.poll_next_unpin(cx)
+    }
+}
+
 #[derive(Debug)]
 #[pin_project]
 pub struct TranscriptionStream {
     #[pin]
     rx: Receiver<Result<StreamResponse>>,
     done: bool,
-    request_id: Uuid,
+    state: Arc<Mutex<StreamState>>,
 }
 
 impl Stream for TranscriptionStream {
@@ -809,8 +1759,11 @@ impl TranscriptionStream {
     ///
     /// A request ID needs to be provided to Deepgram as part of any support
     /// or troubleshooting assistance related to a specific request.
+    /// It is the nil UUID if the server did not send a request ID.
+    ///
+    /// After a reconnect, this is the request ID of the new connection.
     pub fn request_id(&self) -> Uuid {
-        self.request_id
+        lock(&self.state).request_id
     }
 }
 
@@ -880,8 +1833,60 @@ mod file_chunker {
 
 #[cfg(test)]
 mod tests {
-    use super::ControlMessage;
-    use crate::common::options::Options;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    use futures::{SinkExt, StreamExt};
+    use http::header::{HeaderName, HeaderValue, AUTHORIZATION};
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::{
+        tungstenite::protocol::{
+            frame::{
+                coding::{CloseCode, Data, OpCode},
+                CloseFrame, Frame,
+            },
+            Message,
+        },
+        WebSocketStream,
+    };
+
+    use super::{ControlMessage, ReconnectPolicy};
+    use crate::{
+        common::{
+            options::{Encoding, Endpointing, Model, OptionWarning, Options},
+            stream_response::StreamResponse,
+        },
+        mock_server, DeepgramError,
+    };
+
+    const TRANSCRIPT: &str = r#"{
+        "type": "Results",
+        "start": 0.0,
+        "duration": 1.5,
+        "is_final": true,
+        "speech_final": true,
+        "from_finalize": false,
+        "channel": {"alternatives": [{"transcript": "hello", "words": [], "confidence": 0.9}]},
+        "metadata": {
+            "request_id": "00000000-0000-4000-8000-000000000000",
+            "model_info": {"name": "general", "version": "1", "arch": "nova-3"},
+            "model_uuid": "00000000-0000-4000-8000-000000000001"
+        },
+        "channel_index": [0, 1]
+    }"#;
+
+    const METADATA: &str = r#"{
+        "type": "Metadata",
+        "request_id": "00000000-0000-4000-8000-000000000000",
+        "created": "2025-01-01T00:00:00.000Z",
+        "duration": 1.5,
+        "channels": 1
+    }"#;
 
     #[test]
     fn test_stream_url() {
@@ -911,6 +1916,180 @@ mod tests {
         assert_eq!(builder.urlencoded().unwrap(), opts.urlencoded().unwrap())
     }
 
+    #[test]
+    fn streaming_params_win_over_extra_params() {
+        let dg = crate::Deepgram::new("token").unwrap();
+        let opts = Options::builder()
+            .query_params([
+                ("sample_rate".to_string(), "8000".to_string()),
+                ("interim_results".to_string(), "false".to_string()),
+                ("extra".to_string(), "kept".to_string()),
+            ])
+            .build();
+        let transcription = dg.transcription();
+        let builder = transcription
+            .stream_request_with_options(opts)
+            .encoding(Encoding::Linear16)
+            .sample_rate(16000)
+            .interim_results(true);
+        assert_eq!(
+            builder.urlencoded().unwrap(),
+            "extra=kept&encoding=linear16&sample_rate=16000&interim_results=true",
+        );
+    }
+
+    #[test]
+    fn encoding_with_sample_rate() {
+        let dg = crate::Deepgram::new("token").unwrap();
+        let transcription = dg.transcription();
+        for (encoding, sample_rate, expected) in [
+            (Encoding::Mulaw, 8000, "encoding=mulaw&sample_rate=8000"),
+            (Encoding::Alaw, 8000, "encoding=alaw&sample_rate=8000"),
+            (Encoding::Opus, 48000, "encoding=opus&sample_rate=48000"),
+            (Encoding::Flac, 44100, "encoding=flac&sample_rate=44100"),
+            (Encoding::AmrNb, 8000, "encoding=amr-nb&sample_rate=8000"),
+            (Encoding::G729, 8000, "encoding=g729&sample_rate=8000"),
+        ] {
+            let builder = transcription
+                .stream_request()
+                .encoding(encoding)
+                .sample_rate(sample_rate);
+            assert_eq!(builder.urlencoded().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn encoding_requires_sample_rate() {
+        let dg = crate::Deepgram::new("token").unwrap();
+        let transcription = dg.transcription();
+
+        let builder = transcription.stream_request().encoding(Encoding::Linear16);
+        assert!(matches!(
+            builder.validate(),
+            Err(DeepgramError::InvalidOptions(_))
+        ));
+
+        let options = Options::builder().encoding(Encoding::Linear16).build();
+        let builder = transcription.stream_request_with_options(options);
+        assert!(matches!(
+            builder.validate(),
+            Err(DeepgramError::InvalidOptions(_))
+        ));
+
+        let builder = transcription.stream_request().sample_rate(16000);
+        assert!(matches!(
+            builder.validate(),
+            Err(DeepgramError::InvalidOptions(_))
+        ));
+
+        let builder = transcription
+            .stream_request()
+            .encoding(Encoding::Linear16)
+            .sample_rate(16000);
+        assert!(builder.validate().is_ok());
+        assert!(transcription.stream_request().validate().is_ok());
+    }
+
+    #[test]
+    fn sample_rate_warnings() {
+        let dg = crate::Deepgram::new("token").unwrap();
+        let transcription = dg.transcription();
+
+        let builder = transcription
+            .stream_request()
+            .encoding(Encoding::Mulaw)
+            .sample_rate(44100);
+        assert_eq!(
+            builder.warnings(),
+            [OptionWarning::UnusualSampleRate {
+                encoding: Encoding::Mulaw,
+                sample_rate: 44100,
+            }]
+        );
+        assert_eq!(
+            builder.warnings()[0].to_string(),
+            "`mulaw` audio is usually sampled at 8000, 16000 Hz, not 44100 Hz"
+        );
+        // Unusual, but not invalid.
+        assert!(builder.validate().is_ok());
+
+        let options = Options::builder().encoding(Encoding::AmrWb).build();
+        let builder = transcription
+            .stream_request_with_options(options)
+            .sample_rate(8000);
+        assert_eq!(builder.warnings().len(), 1);
+
+        for (encoding, sample_rate) in [
+            (Encoding::Mulaw, 8000),
+            (Encoding::Opus, 48000),
+            (Encoding::Linear16, 44100),
+        ] {
+            let builder = transcription
+                .stream_request()
+                .encoding(encoding)
+                .sample_rate(sample_rate);
+            assert!(builder.warnings().is_empty());
+        }
+        assert!(transcription.stream_request().warnings().is_empty());
+    }
+
+    #[test]
+    fn flux_incompatible_options() {
+        let dg = crate::Deepgram::new("token").unwrap();
+        let transcription = dg.transcription();
+        let flux = || {
+            transcription
+                .stream_request_with_options(Options::builder().model(Model::FluxGeneralEn).build())
+        };
+        assert!(flux().validate().is_ok());
+
+        for (builder, option) in [
+            (flux().interim_results(true), "interim_results"),
+            (flux().endpointing(Endpointing::Disabled), "endpointing"),
+            (flux().keep_alive(), "keep_alive"),
+        ] {
+            match builder.validate() {
+                Err(DeepgramError::InvalidOptions(message)) => {
+                    assert!(message.contains(option), "{message}")
+                }
+                other => panic!("expected {option} to be rejected, got {other:?}"),
+            }
+        }
+
+        // The same options are fine with other models.
+        let builder = transcription
+            .stream_request_with_options(Options::builder().model(Model::Nova3).build())
+            .interim_results(true)
+            .endpointing(Endpointing::Disabled)
+            .keep_alive();
+        assert!(builder.validate().is_ok());
+    }
+
+    #[test]
+    fn dictation_and_ner() {
+        let dg = crate::Deepgram::new("token").unwrap();
+        let transcription = dg.transcription();
+        let options = Options::builder().dictation(true).ner(true).build();
+        let builder = transcription.stream_request_with_options(options);
+        assert_eq!(builder.urlencoded().unwrap(), "dictation=true&ner=true");
+    }
+
+    #[test]
+    fn channels_with_multichannel() {
+        let dg = crate::Deepgram::new("token").unwrap();
+        let transcription = dg.transcription();
+        let options = Options::builder().multichannel(true).build();
+        let builder = transcription
+            .stream_request_with_options(options)
+            .encoding(Encoding::Linear16)
+            .sample_rate(16000)
+            .channels(2);
+        assert_eq!(
+            builder.urlencoded().unwrap(),
+            "multichannel=true&encoding=linear16&sample_rate=16000&channels=2"
+        );
+    }
+
     #[test]
     fn control_message_format() {
         assert_eq!(
@@ -918,4 +2097,917 @@ mod tests {
             r#"{"type":"CloseStream"}"#
         );
     }
+
+    #[tokio::test]
+    async fn handle_as_stream_and_sink() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            while let Some(Ok(message)) = ws.next().await {
+                match message {
+                    Message::Binary(_) => ws.send(Message::text(TRANSCRIPT)).await.unwrap(),
+                    Message::Text(text) if text.contains("CloseStream") => {
+                        ws.send(Message::text(METADATA)).await.unwrap();
+                        ws.close(None).await.unwrap();
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let handle = dg.transcription().stream_request().handle().await.unwrap();
+        let (mut audio, mut responses) = StreamExt::split(handle);
+        let sender = tokio::spawn(async move {
+            for _ in 0..2 {
+                audio
+                    .send(bytes::Bytes::from_static(&[0; 320]))
+                    .await
+                    .unwrap();
+            }
+            audio.close().await.unwrap();
+        });
+
+        assert!(matches!(
+            responses.next().await,
+            Some(Ok(StreamResponse::TranscriptResponse { .. }))
+        ));
+        let rest: Vec<_> = responses.map(Result::unwrap).collect().await;
+        assert!(matches!(
+            rest[..],
+            [
+                StreamResponse::TranscriptResponse { .. },
+                StreamResponse::MetadataResponse { .. }
+            ]
+        ));
+        sender.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn split_handle() {
+        let (audio_tx, audio_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::websocket(move |_, mut ws| {
+            let audio_tx = audio_tx.clone();
+            async move {
+                while let Some(Ok(message)) = ws.next().await {
+                    match message {
+                        Message::Binary(audio) => {
+                            audio_tx.unbounded_send(audio.len()).unwrap();
+                            ws.send(Message::text(TRANSCRIPT)).await.unwrap();
+                        }
+                        Message::Text(text) if text.contains("CloseStream") => {
+                            ws.send(Message::text(METADATA)).await.unwrap();
+                            ws.close(None).await.unwrap();
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let handle = dg.transcription().stream_request().handle().await.unwrap();
+        let (mut audio, responses) = handle.split();
+        let sender = tokio::spawn(async move {
+            for _ in 0..3 {
+                audio
+                    .send(bytes::Bytes::from_static(&[0; 320]))
+                    .await
+                    .unwrap();
+            }
+            // Dropping the sink closes the stream.
+        });
+        let receiver = tokio::spawn(responses.map(Result::unwrap).collect::<Vec<_>>());
+
+        sender.await.unwrap();
+        let responses = receiver.await.unwrap();
+        assert_eq!(responses.len(), 4);
+        assert!(responses[..3]
+            .iter()
+            .all(|response| matches!(response, StreamResponse::TranscriptResponse { .. })));
+        assert!(responses[3].is_metadata());
+        assert_eq!(audio_rx.take(3).collect::<Vec<_>>().await, [320; 3]);
+    }
+
+    #[tokio::test]
+    async fn control_messages_sent() {
+        let (message_tx, message_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::websocket(move |_, mut ws| {
+            let message_tx = message_tx.clone();
+            async move {
+                while let Some(Ok(message)) = ws.next().await {
+                    if let Message::Text(text) = message {
+                        message_tx.unbounded_send(text.to_string()).unwrap();
+                        if text.contains("CloseStream") {
+                            ws.send(Message::text(METADATA)).await.unwrap();
+                        }
+                    }
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        handle.finalize().await.unwrap();
+        handle.keep_alive().await.unwrap();
+        handle.close_stream().await.unwrap();
+
+        let messages: Vec<_> = message_rx.take(3).collect().await;
+        assert_eq!(
+            messages,
+            [
+                r#"{"type":"Finalize"}"#,
+                r#"{"type":"KeepAlive"}"#,
+                r#"{"type":"CloseStream"}"#
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn close_stream_and_wait_returns_terminal_response() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            while let Some(Ok(message)) = ws.next().await {
+                if message == Message::text(r#"{"type":"CloseStream"}"#) {
+                    ws.send(Message::text(TRANSCRIPT)).await.unwrap();
+                    ws.send(Message::text(METADATA)).await.unwrap();
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        assert_eq!(handle.request_id().to_string(), mock_server::REQUEST_ID);
+        handle.send_data(vec![0; 320]).await.unwrap();
+
+        let terminal = handle.close_stream_and_wait().await.unwrap();
+        assert!(matches!(
+            terminal,
+            Some(StreamResponse::MetadataResponse { duration, channels: 1, .. }) if duration == 1.5
+        ));
+
+        // Results that arrived before the terminal response are still delivered.
+        assert!(matches!(
+            handle.receive().await,
+            Some(Ok(StreamResponse::TranscriptResponse { .. }))
+        ));
+        assert!(handle.receive().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn receive_final_skips_interim_results() {
+        let interim = TRANSCRIPT.replace(r#""is_final": true"#, r#""is_final": false"#);
+        let base_url = mock_server::websocket(move |_, mut ws| {
+            let interim = interim.clone();
+            async move {
+                for message in [&interim, TRANSCRIPT, &interim, METADATA] {
+                    ws.send(Message::text(message)).await.unwrap();
+                }
+                ws.close(None).await.unwrap();
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        let responses: Vec<_> = handle.receive_final().collect().await;
+
+        assert_eq!(responses.len(), 2);
+        assert!(matches!(
+            responses[0],
+            Ok(StreamResponse::TranscriptResponse { is_final: true, .. })
+        ));
+        assert!(matches!(
+            responses[1],
+            Ok(StreamResponse::MetadataResponse { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn receive_timeout() {
+        let (release_tx, release_rx) = futures::channel::oneshot::channel::<()>();
+        let release_rx = Arc::new(Mutex::new(Some(release_rx)));
+        let base_url = mock_server::websocket(move |_, mut ws| {
+            let release_rx = release_rx.lock().unwrap().take().unwrap();
+            async move {
+                // Withhold every message until released.
+                let _ = release_rx.await;
+                ws.send(Message::text(TRANSCRIPT)).await.unwrap();
+                ws.close(None).await.unwrap();
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+
+        let timeout = Duration::from_millis(50);
+        assert!(matches!(
+            handle.receive_timeout(timeout).await,
+            Err(DeepgramError::ReceiveTimeout(t)) if t == timeout
+        ));
+
+        release_tx.send(()).unwrap();
+        let timeout = Duration::from_secs(5);
+        assert!(matches!(
+            handle.receive_timeout(timeout).await,
+            Ok(Some(StreamResponse::TranscriptResponse { .. }))
+        ));
+        assert!(matches!(handle.receive_timeout(timeout).await, Ok(None)));
+    }
+
+    #[tokio::test]
+    async fn on_raw_frame_sees_every_message() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            for message in [TRANSCRIPT, "not json", METADATA] {
+                ws.send(Message::text(message)).await.unwrap();
+            }
+            ws.close(None).await.unwrap();
+        })
+        .await;
+
+        let frames = Arc::new(Mutex::new(Vec::new()));
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg
+            .transcription()
+            .stream_request()
+            .on_raw_frame({
+                let frames = Arc::clone(&frames);
+                move |frame| frames.lock().unwrap().push(frame.to_vec())
+            })
+            .handle()
+            .await
+            .unwrap();
+        while handle.receive().await.is_some() {}
+
+        assert_eq!(
+            *frames.lock().unwrap(),
+            [TRANSCRIPT, "not json", METADATA].map(|message| message.as_bytes().to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn large_messages() {
+        // Over tungstenite's default 16 MiB frame limit, sent as a single frame.
+        let transcript = "a".repeat(20 << 20);
+        let message = TRANSCRIPT.replace("hello", &transcript);
+        let base_url = mock_server::websocket(move |_, mut ws| {
+            let message = message.clone();
+            async move {
+                ws.send(Message::text(message)).await.unwrap();
+                let _ = ws.close(None).await;
+            }
+        })
+        .await;
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        let Some(Ok(StreamResponse::TranscriptResponse { channel, .. })) = handle.receive().await
+        else {
+            panic!("expected the large transcript");
+        };
+        assert_eq!(channel.alternatives[0].transcript, transcript);
+
+        let mut handle = dg
+            .transcription()
+            .stream_request()
+            .max_message_size(1 << 20)
+            .handle()
+            .await
+            .unwrap();
+        assert!(matches!(
+            handle.receive().await,
+            Some(Err(DeepgramError::WsError(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn ping() {
+        let (close_tx, close_rx) = futures::channel::oneshot::channel::<()>();
+        let close_rx = std::sync::Mutex::new(Some(close_rx));
+        let base_url = mock_server::websocket(move |_, mut ws| {
+            let close_rx = close_rx.lock().unwrap().take().unwrap();
+            async move {
+                tokio::pin!(close_rx);
+                loop {
+                    // Reading answers pings with pongs.
+                    tokio::select! {
+                        message = ws.next() => if message.is_none() { return },
+                        _ = &mut close_rx => break,
+                    }
+                }
+                ws.close(None).await.unwrap();
+                while ws.next().await.is_some() {}
+            }
+        })
+        .await;
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        assert!(handle.is_connected());
+        handle.ping().await.unwrap();
+        handle.ping().await.unwrap();
+        assert!(handle.is_connected());
+
+        close_tx.send(()).unwrap();
+        assert!(handle.receive().await.is_none());
+        assert!(!handle.is_connected());
+        assert!(handle.ping().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn connect_timeout() {
+        // The kernel accepts the connection, but the upgrade is never answered.
+        // Unroutable addresses are not reliably unroutable in every sandbox.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let dg = crate::Deepgram::builder()
+            .base_url(base_url.parse().unwrap())
+            .connect_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let err = dg
+            .transcription()
+            .stream_request()
+            .handle()
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DeepgramError::ConnectTimeout(timeout) if timeout == Duration::from_millis(100)),
+            "{err:?}"
+        );
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn idle_timeout() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            while let Some(Ok(message)) = ws.next().await {
+                if let Message::Binary(_) = message {
+                    ws.send(Message::text(TRANSCRIPT)).await.unwrap();
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let idle_timeout = Duration::from_millis(50);
+        let mut handle = dg
+            .transcription()
+            .stream_request()
+            .idle_timeout(idle_timeout)
+            .handle()
+            .await
+            .unwrap();
+
+        let Some(Ok(StreamResponse::Idle { duration })) = handle.receive().await else {
+            panic!("expected an idle event");
+        };
+        assert!(duration >= idle_timeout);
+        // Only once per idle period.
+        assert!(matches!(
+            handle.receive_timeout(idle_timeout * 3).await,
+            Err(DeepgramError::ReceiveTimeout(_))
+        ));
+
+        // Traffic starts a new period.
+        handle.send_data(vec![0; 320]).await.unwrap();
+        assert!(matches!(
+            handle.receive().await,
+            Some(Ok(StreamResponse::TranscriptResponse { .. }))
+        ));
+        assert!(matches!(
+            handle.receive().await,
+            Some(Ok(StreamResponse::Idle { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn malformed_message_keeps_raw_text() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            for message in [r#"{"type": "Results", "#, TRANSCRIPT] {
+                ws.send(Message::text(message)).await.unwrap();
+            }
+            ws.close(None).await.unwrap();
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+
+        let Some(Err(DeepgramError::DeserializationError { raw, .. })) = handle.receive().await
+        else {
+            panic!("expected a deserialization error");
+        };
+        assert_eq!(raw, r#"{"type": "Results", "#);
+        // The stream carries on after the malformed message.
+        assert!(matches!(
+            handle.receive().await,
+            Some(Ok(StreamResponse::TranscriptResponse { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn fragmented_messages() {
+        /// Send `message` as a text frame followed by a continuation frame.
+        async fn send_fragmented(ws: &mut WebSocketStream<TcpStream>, message: &str) {
+            let (first, rest) = message.split_at(message.len() / 2);
+            let frames = [
+                Frame::message(first.to_string(), OpCode::Data(Data::Text), false),
+                Frame::message(rest.to_string(), OpCode::Data(Data::Continue), true),
+            ];
+            for frame in frames {
+                ws.send(Message::Frame(frame)).await.unwrap();
+            }
+        }
+
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            while let Some(Ok(message)) = ws.next().await {
+                if message == Message::text(r#"{"type":"CloseStream"}"#) {
+                    send_fragmented(&mut ws, r#"{"type": "Results", "start": "oops"}"#).await;
+                    send_fragmented(&mut ws, METADATA).await;
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+
+        // The terminal response still closes the connection cleanly.
+        assert!(handle.close_stream_and_wait().await.unwrap().is_some());
+        assert!(handle.close_frame().unwrap().is_normal());
+
+        let Some(Err(DeepgramError::DeserializationError { raw, .. })) = handle.receive().await
+        else {
+            panic!("expected a deserialization error");
+        };
+        assert_eq!(raw, r#"{"type": "Results", "start": "oops"}"#);
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_dropped_connection() {
+        const NEW_REQUEST_ID: &str = "00000000-0000-4000-8000-000000000001";
+        let connections = Arc::new(AtomicUsize::new(0));
+        let request_ids = |connection| match connection {
+            0 => mock_server::REQUEST_ID.to_string(),
+            _ => NEW_REQUEST_ID.to_string(),
+        };
+        let base_url = mock_server::websocket_with_request_ids(request_ids, {
+            let connections = Arc::clone(&connections);
+            move |_, mut ws| {
+                let connection = connections.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    while let Some(Ok(message)) = ws.next().await {
+                        match message {
+                            // Drop the first connection without a close handshake.
+                            Message::Binary(_) if connection == 0 => return,
+                            Message::Binary(_) => ws.send(Message::text(TRANSCRIPT)).await.unwrap(),
+                            Message::Text(text) if text.as_str() == r#"{"type":"CloseStream"}"# => {
+                                ws.send(Message::text(METADATA)).await.unwrap()
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg
+            .transcription()
+            .stream_request()
+            .reconnect(ReconnectPolicy::new(3, Duration::from_millis(10)))
+            .handle()
+            .await
+            .unwrap();
+        assert_eq!(handle.request_id().to_string(), mock_server::REQUEST_ID);
+
+        handle.send_data(vec![0; 320]).await.unwrap();
+        match handle.receive().await {
+            Some(Ok(StreamResponse::Reconnected {
+                attempt: 1,
+                request_id,
+            })) => assert_eq!(request_id.to_string(), NEW_REQUEST_ID),
+            other => panic!("expected a reconnection, got {other:?}"),
+        }
+        assert_eq!(handle.request_id().to_string(), NEW_REQUEST_ID);
+
+        handle.send_data(vec![0; 320]).await.unwrap();
+        assert!(matches!(
+            handle.receive().await,
+            Some(Ok(StreamResponse::TranscriptResponse { .. }))
+        ));
+        assert!(matches!(
+            handle.close_stream_and_wait().await.unwrap(),
+            Some(StreamResponse::MetadataResponse { .. })
+        ));
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn traces_stream_lifecycle() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            while let Some(Ok(message)) = ws.next().await {
+                if message == Message::text(r#"{"type":"CloseStream"}"#) {
+                    ws.send(Message::text(TRANSCRIPT)).await.unwrap();
+                    ws.send(Message::text(METADATA)).await.unwrap();
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        assert!(logs_contain(&format!(
+            "deepgram_listen{{request_id={}}}",
+            mock_server::REQUEST_ID
+        )));
+        assert!(logs_contain("connected"));
+
+        handle.send_data(vec![0; 320]).await.unwrap();
+        handle.close_stream_and_wait().await.unwrap();
+        assert!(logs_contain("bytes_sent=320"));
+        assert!(logs_contain("message_type=\"Results\""));
+        assert!(logs_contain("message_type=\"Metadata\""));
+    }
+
+    #[tokio::test]
+    async fn metrics_count_traffic() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            while let Some(Ok(message)) = ws.next().await {
+                if let Message::Binary(_) = message {
+                    ws.send(Message::text(TRANSCRIPT)).await.unwrap();
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        assert_eq!(handle.metrics(), Default::default());
+
+        handle.send_data(vec![0; 320]).await.unwrap();
+        handle.send_data(vec![0; 160]).await.unwrap();
+        for _ in 0..2 {
+            handle.receive().await.unwrap().unwrap();
+        }
+
+        let metrics = handle.metrics();
+        assert_eq!(metrics.bytes_sent, 480);
+        assert_eq!(metrics.messages_received, 2);
+        assert_eq!(metrics.messages_by_type.get("Results"), Some(&2));
+        assert!(metrics.time_to_first_transcript.is_some());
+    }
+
+    #[tokio::test]
+    async fn send_data_applies_backpressure() {
+        // A server that never reads, so the connection stops draining.
+        let base_url = mock_server::websocket(|_, ws| async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            drop(ws);
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg
+            .transcription()
+            .stream_request()
+            .outbound_buffer(1)
+            .handle()
+            .await
+            .unwrap();
+
+        let chunk = bytes::Bytes::from(vec![0; 1 << 20]);
+        let mut sent = 0;
+        while tokio::time::timeout(Duration::from_millis(200), handle.send_data(chunk.clone()))
+            .await
+            .is_ok()
+        {
+            sent += 1;
+            // Only the socket buffers and the queue should be able to hold audio.
+            assert!(sent < 256, "send_data never blocked");
+        }
+    }
+
+    #[tokio::test]
+    async fn caches_model_info() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            while let Some(Ok(message)) = ws.next().await {
+                if let Message::Binary(_) = message {
+                    ws.send(Message::text(TRANSCRIPT)).await.unwrap();
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        assert!(handle.model_info().is_none());
+
+        handle.send_data(vec![0; 320]).await.unwrap();
+        handle.receive().await.unwrap().unwrap();
+
+        let model_info = handle.model_info().unwrap();
+        assert_eq!(model_info.name, "general");
+        assert_eq!(model_info.arch, "nova-3");
+        assert_eq!(
+            handle.model_uuid().as_deref(),
+            Some("00000000-0000-4000-8000-000000000001")
+        );
+    }
+
+    #[tokio::test]
+    async fn captures_close_frame() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            ws.close(Some(CloseFrame {
+                code: CloseCode::Error,
+                reason: "internal error".into(),
+            }))
+            .await
+            .unwrap();
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        assert!(matches!(
+            handle.receive().await,
+            Some(Err(DeepgramError::WebsocketClose { code: 1011, .. }))
+        ));
+        assert!(handle.receive().await.is_none());
+
+        let frame = handle.close_frame().unwrap();
+        assert_eq!(frame.code, 1011);
+        assert_eq!(frame.reason, "internal error");
+        assert!(!frame.is_normal());
+    }
+
+    #[tokio::test]
+    async fn close_stream_records_normal_close() {
+        let base_url = mock_server::websocket(|_, mut ws| async move {
+            while let Some(Ok(message)) = ws.next().await {
+                if message == Message::text(r#"{"type":"CloseStream"}"#) {
+                    ws.send(Message::text(METADATA)).await.unwrap();
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+        assert!(handle.close_frame().is_none());
+        handle.close_stream_and_wait().await.unwrap();
+        assert!(handle.close_frame().unwrap().is_normal());
+    }
+
+    #[test]
+    fn reconnect_backoff() {
+        let policy = ReconnectPolicy::new(10, Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500));
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn pipe_from_sends_all_audio() {
+        let received = Arc::new(AtomicUsize::new(0));
+        let base_url = mock_server::websocket({
+            let received = Arc::clone(&received);
+            move |_, mut ws| {
+                let received = Arc::clone(&received);
+                async move {
+                    while let Some(Ok(message)) = ws.next().await {
+                        match message {
+                            Message::Binary(audio) => {
+                                received.fetch_add(audio.len(), Ordering::SeqCst);
+                            }
+                            Message::Text(text) if text.as_str() == r#"{"type":"CloseStream"}"# => {
+                                ws.send(Message::text(METADATA)).await.unwrap()
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+
+        let pcm = std::io::Cursor::new(vec![7u8; 100_000]);
+        handle.pipe_from(pcm).await.unwrap();
+        assert!(handle.close_stream_and_wait().await.unwrap().is_some());
+        assert_eq!(received.load(Ordering::SeqCst), 100_000);
+    }
+
+    #[tokio::test]
+    async fn preserves_frame_boundaries() {
+        let frames = Arc::new(Mutex::new(Vec::new()));
+        let base_url = mock_server::websocket({
+            let frames = Arc::clone(&frames);
+            move |_, mut ws| {
+                let frames = Arc::clone(&frames);
+                async move {
+                    while let Some(Ok(message)) = ws.next().await {
+                        match message {
+                            Message::Binary(frame) => frames.lock().unwrap().push(frame),
+                            Message::Text(text) if text.as_str() == r#"{"type":"CloseStream"}"# => {
+                                ws.send(Message::text(METADATA)).await.unwrap()
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let mut handle = dg.transcription().stream_request().handle().await.unwrap();
+
+        let sizes = [3, 160, 1, 4000, 57];
+        let mut packets = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &size)| bytes::Bytes::from(vec![i as u8; size]));
+        handle.send_data(packets.next().unwrap()).await.unwrap();
+        handle.send_frames(packets).await.unwrap();
+        assert!(handle.close_stream_and_wait().await.unwrap().is_some());
+
+        let frames = frames.lock().unwrap();
+        assert_eq!(frames.iter().map(|f| f.len()).collect::<Vec<_>>(), sizes);
+        for (i, frame) in frames.iter().enumerate() {
+            assert!(frame.iter().all(|&byte| byte == i as u8));
+        }
+    }
+
+    #[tokio::test]
+    async fn send_data_does_not_copy_bytes() {
+        let (message_tx, mut message_rx) = futures::channel::mpsc::channel(1);
+        let (_response_tx, response_rx) = futures::channel::mpsc::channel(1);
+        let mut handle = super::WebsocketHandle {
+            message_tx,
+            response_rx,
+            pending: Default::default(),
+            state: Default::default(),
+        };
+
+        let audio = bytes::Bytes::from(vec![1u8; 4096]);
+        handle.send_data(audio.clone()).await.unwrap();
+        match message_rx.next().await {
+            Some(super::WsMessage::Audio(sent)) => assert_eq!(sent.as_ptr(), audio.as_ptr()),
+            other => panic!("expected audio, got {other:?}"),
+        }
+
+        // Vec<u8> still works, and its allocation is reused as well.
+        let audio = vec![2u8; 4096];
+        let ptr = audio.as_ptr();
+        handle.send_data(audio).await.unwrap();
+        match message_rx.next().await {
+            Some(super::WsMessage::Audio(sent)) => assert_eq!(sent.as_ptr(), ptr),
+            other => panic!("expected audio, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn request_id_without_header() {
+        // Unlike the mock server, answer the upgrade without `dg-request-id`.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(_)) = ws.next().await {}
+        });
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let handle = dg.transcription().stream_request().handle().await.unwrap();
+        assert!(handle.request_id().is_nil());
+    }
+
+    #[tokio::test]
+    async fn dials_configured_base_url() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::websocket(move |request, _| {
+            request_tx.unbounded_send(request).unwrap();
+            async {}
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url_and_api_key(base_url.as_str(), "token").unwrap();
+        dg.transcription()
+            .stream_request()
+            .encoding(Encoding::Linear16)
+            .sample_rate(16000)
+            .handle()
+            .await
+            .unwrap();
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "/transcription/v1/listen?encoding=linear16&sample_rate=16000"
+        );
+        assert_eq!(
+            request.headers()["host"],
+            base_url.trim_start_matches("http://")
+        );
+        assert_eq!(request.headers()["authorization"], "Token token");
+    }
+
+    #[tokio::test]
+    async fn custom_headers_on_upgrade_request() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::websocket(move |request, _| {
+            request_tx.unbounded_send(request).unwrap();
+            async {}
+        })
+        .await;
+
+        let dg = crate::Deepgram::builder()
+            .base_url(base_url.parse().unwrap())
+            .api_key("token")
+            .header(
+                HeaderName::from_static("x-tenant-id"),
+                HeaderValue::from_static("acme"),
+            )
+            .header(AUTHORIZATION, HeaderValue::from_static("Token stolen"))
+            .build()
+            .unwrap();
+        dg.transcription().stream_request().handle().await.unwrap();
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.headers()["x-tenant-id"], "acme");
+        let authorization: Vec<_> = request.headers().get_all(AUTHORIZATION).iter().collect();
+        assert_eq!(authorization, ["Token token"]);
+    }
+
+    #[tokio::test]
+    async fn user_agent_on_upgrade_request() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::websocket(move |request, _| {
+            request_tx.unbounded_send(request).unwrap();
+            async {}
+        })
+        .await;
+
+        let dg = crate::Deepgram::builder()
+            .base_url(base_url.parse().unwrap())
+            .user_agent("acme-voice/2.1")
+            .build()
+            .unwrap();
+        dg.transcription().stream_request().handle().await.unwrap();
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.headers()["user-agent"], "acme-voice/2.1");
+    }
+
+    #[tokio::test]
+    async fn callback_in_dialed_url() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::websocket(move |request, _| {
+            request_tx.unbounded_send(request).unwrap();
+            async {}
+        })
+        .await;
+
+        let dg = crate::Deepgram::with_base_url(base_url.as_str()).unwrap();
+        dg.transcription()
+            .stream_request()
+            .callback("https://example.com/webhook?id=1".parse().unwrap())
+            .handle()
+            .await
+            .unwrap();
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(
+            request.uri().query(),
+            Some("callback=https%3A%2F%2Fexample.com%2Fwebhook%3Fid%3D1")
+        );
+    }
+
+    #[tokio::test]
+    async fn connects_through_proxy() {
+        let base_url = mock_server::websocket(|_, _| async {}).await;
+        let (proxy, mut targets) =
+            mock_server::connect_proxy(base_url.trim_start_matches("http://").to_owned()).await;
+
+        let dg = crate::Deepgram::builder()
+            .base_url("http://deepgram.invalid:8080".parse().unwrap())
+            .proxy(proxy.parse().unwrap())
+            .build()
+            .unwrap();
+        let handle = dg.transcription().stream_request().handle().await.unwrap();
+
+        assert_eq!(handle.request_id().to_string(), mock_server::REQUEST_ID);
+        assert_eq!(targets.next().await.unwrap(), "deepgram.invalid:8080");
+    }
 }
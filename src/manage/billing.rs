@@ -67,9 +67,11 @@ impl Billing<'_> {
     /// # }
     /// ```
     pub async fn list_balance(&self, project_id: &str) -> crate::Result<Balances> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/balances",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/balances"));
 
-        send_and_translate_response(self.0.client.get(url)).await
+        send_and_translate_response(self.0.inner.client.get(url)).await
     }
 
     /// Get the details of a specific balance.
@@ -108,10 +110,11 @@ impl Billing<'_> {
     /// # }
     /// ```
     pub async fn get_balance(&self, project_id: &str, balance_id: &str) -> crate::Result<Balance> {
-        let url =
-            format!("https://api.deepgram.com/v1/projects/{project_id}/balances/{balance_id}",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/balances/{balance_id}"));
 
-        send_and_translate_response(self.0.client.get(url)).await
+        send_and_translate_response(self.0.inner.client.get(url)).await
     }
 }
 
@@ -6,8 +6,10 @@
 
 use crate::{send_and_translate_response, Deepgram};
 
-use response::Message;
+use options::{Options, SerializableOptions};
+use response::{Invites, Message};
 
+pub mod options;
 pub mod response;
 
 /// Manage the invitations to a Deepgram Project.
@@ -67,8 +69,138 @@ impl Invitations<'_> {
     /// # }
     /// ```
     pub async fn leave_project(&self, project_id: &str) -> crate::Result<Message> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/leave",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/leave"));
 
-        send_and_translate_response(self.0.client.delete(url)).await
+        send_and_translate_response(self.0.inner.client.delete(url)).await
+    }
+
+    /// Get the pending invitations to the specified project.
+    ///
+    /// See the [Deepgram API Reference][api] for more info.
+    ///
+    /// [api]: https://developers.deepgram.com/reference/management-api/invitations/list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// # let project_id =
+    /// #     env::var("DEEPGRAM_PROJECT_ID").expect("DEEPGRAM_PROJECT_ID environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let invites = dg_client
+    ///     .invitations()
+    ///     .list_invites(&project_id)
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_invites(&self, project_id: &str) -> crate::Result<Invites> {
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/invites"));
+
+        send_and_translate_response(self.0.inner.client.get(url)).await
+    }
+
+    /// Invite someone to the specified project.
+    ///
+    /// See the [Deepgram API Reference][api] for more info.
+    ///
+    /// [api]: https://developers.deepgram.com/reference/management-api/invitations/create
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{manage::invitations::options::Options, Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// # let project_id =
+    /// #     env::var("DEEPGRAM_PROJECT_ID").expect("DEEPGRAM_PROJECT_ID environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let options = Options::builder("ada@example.com", "member").build();
+    /// dg_client
+    ///     .invitations()
+    ///     .send_invite(&project_id, &options)
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_invite(&self, project_id: &str, options: &Options) -> crate::Result<Message> {
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/invites"));
+        let request = self
+            .0
+            .inner
+            .client
+            .post(url)
+            .json(&SerializableOptions::from(options));
+
+        send_and_translate_response(request).await
+    }
+
+    /// Cancel the pending invitation of `email` to the specified project.
+    ///
+    /// See the [Deepgram API Reference][api] for more info.
+    ///
+    /// [api]: https://developers.deepgram.com/reference/management-api/invitations/delete
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// # let project_id =
+    /// #     env::var("DEEPGRAM_PROJECT_ID").expect("DEEPGRAM_PROJECT_ID environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// dg_client
+    ///     .invitations()
+    ///     .delete_invite(&project_id, "ada@example.com")
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_invite(&self, project_id: &str, email: &str) -> crate::Result<Message> {
+        let mut url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/invites"));
+        // Pushing the email as a path segment escapes any reserved characters in it.
+        url.path_segments_mut()
+            .expect("management API URLs can be a base")
+            .push(email);
+
+        send_and_translate_response(self.0.inner.client.delete(url)).await
     }
 }
@@ -0,0 +1,94 @@
+//! Set options for [`Invitations::send_invite`](super::Invitations::send_invite).
+//!
+//! See the [Deepgram API Reference][api] for more info.
+//!
+//! [api]: https://developers.deepgram.com/reference/management-api/invitations/create
+
+use serde::Serialize;
+
+/// Used as a parameter for [`Invitations::send_invite`](super::Invitations::send_invite).
+///
+/// See the [Deepgram API Reference][api] for more info.
+///
+/// [api]: https://developers.deepgram.com/reference/management-api/invitations/create
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Options {
+    email: String,
+    scope: String,
+}
+
+/// Builds an [`Options`] object using [the Builder pattern][builder].
+///
+/// [builder]: https://rust-unofficial.github.io/patterns/patterns/creational/builder.html
+#[derive(Debug, PartialEq, Clone)]
+pub struct OptionsBuilder(Options);
+
+#[derive(Serialize)]
+pub(crate) struct SerializableOptions<'a> {
+    email: &'a String,
+    scope: &'a String,
+}
+
+impl Options {
+    /// Construct a new [`OptionsBuilder`].
+    pub fn builder(email: impl Into<String>, scope: impl Into<String>) -> OptionsBuilder {
+        OptionsBuilder::new(email, scope)
+    }
+
+    /// Return the Options in json format. If serialization would
+    /// fail, this will also return an error.
+    ///
+    /// This is intended primarily to help with debugging API requests.
+    ///
+    /// ```
+    /// use deepgram::manage::invitations::options::Options;
+    /// let options = Options::builder("ada@example.com", "member").build();
+    /// assert_eq!(
+    ///     &options.json().unwrap(),
+    ///     r#"{"email":"ada@example.com","scope":"member"}"#)
+    /// ```
+    ///
+    pub fn json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&SerializableOptions::from(self))
+    }
+}
+
+impl OptionsBuilder {
+    /// Construct a new [`OptionsBuilder`].
+    pub fn new(email: impl Into<String>, scope: impl Into<String>) -> Self {
+        Self(Options {
+            email: email.into(),
+            scope: scope.into(),
+        })
+    }
+
+    /// Set the email address to invite.
+    ///
+    /// This will overwrite the one set in [`OptionsBuilder::new`] for [`Options::builder`].
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.0.email = email.into();
+        self
+    }
+
+    /// Set the scope the invited member will have, such as `member` or `admin`.
+    ///
+    /// This will overwrite the one set in [`OptionsBuilder::new`] for [`Options::builder`].
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.0.scope = scope.into();
+        self
+    }
+
+    /// Finish building the [`Options`] object.
+    pub fn build(self) -> Options {
+        self.0
+    }
+}
+
+impl<'a> From<&'a Options> for SerializableOptions<'a> {
+    fn from(options: &'a Options) -> Self {
+        // Destructuring it makes sure that we don't forget to use any of it
+        let Options { email, scope } = options;
+
+        Self { email, scope }
+    }
+}
@@ -13,3 +13,30 @@ pub struct Message {
     #[allow(missing_docs)]
     pub message: String,
 }
+
+/// Returned by [`Invitations::list_invites`](super::Invitations::list_invites).
+///
+/// See the [Deepgram API Reference][api] for more info.
+///
+/// [api]: https://developers.deepgram.com/reference/management-api/invitations/list
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Invites {
+    #[allow(missing_docs)]
+    pub invites: Vec<Invite>,
+}
+
+/// A pending invitation to a project.
+///
+/// See the [Deepgram API Reference][api] for more info.
+///
+/// [api]: https://developers.deepgram.com/reference/management-api/invitations/list
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Invite {
+    #[allow(missing_docs)]
+    pub email: String,
+
+    #[allow(missing_docs)]
+    pub scope: String,
+}
@@ -74,9 +74,9 @@ impl Keys<'_> {
     /// # }
     /// ```
     pub async fn list(&self, project_id: &str) -> crate::Result<MembersAndApiKeys> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/keys");
+        let url = self.0.manage_url(&format!("v1/projects/{project_id}/keys"));
 
-        send_and_translate_response(self.0.client.get(url)).await
+        send_and_translate_response(self.0.inner.client.get(url)).await
     }
 
     /// Get details of the specified key.
@@ -113,9 +113,11 @@ impl Keys<'_> {
     /// # }
     /// ```
     pub async fn get(&self, project_id: &str, key_id: &str) -> crate::Result<MemberAndApiKey> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/keys/{key_id}",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/keys/{key_id}"));
 
-        send_and_translate_response(self.0.client.get(url)).await
+        send_and_translate_response(self.0.inner.client.get(url)).await
     }
 
     /// Create a new key in the specified project.
@@ -153,9 +155,10 @@ impl Keys<'_> {
     /// # }
     /// ```
     pub async fn create(&self, project_id: &str, options: &Options) -> crate::Result<NewApiKey> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/keys");
+        let url = self.0.manage_url(&format!("v1/projects/{project_id}/keys"));
         let request = self
             .0
+            .inner
             .client
             .post(url)
             .json(&SerializableOptions::from(options));
@@ -197,8 +200,10 @@ impl Keys<'_> {
     /// # }
     /// ```
     pub async fn delete(&self, project_id: &str, key_id: &str) -> crate::Result<Message> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/keys/{key_id}",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/keys/{key_id}"));
 
-        send_and_translate_response(self.0.client.delete(url)).await
+        send_and_translate_response(self.0.inner.client.delete(url)).await
     }
 }
@@ -101,7 +101,10 @@ pub struct NewApiKey {
     #[allow(missing_docs)]
     pub api_key_id: Uuid,
 
-    #[allow(missing_docs)]
+    /// The secret API key.
+    ///
+    /// Deepgram only returns the secret when the key is created; it cannot be
+    /// retrieved later, so store it before discarding this response.
     pub key: String,
 
     #[allow(missing_docs)]
@@ -67,9 +67,11 @@ impl Members<'_> {
     /// # }
     /// ```
     pub async fn list_members(&self, project_id: &str) -> crate::Result<response::Members> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/members",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/members"));
 
-        send_and_translate_response(self.0.client.get(url)).await
+        send_and_translate_response(self.0.inner.client.get(url)).await
     }
 
     /// Remove the specified member from the specified project.
@@ -107,8 +109,10 @@ impl Members<'_> {
     /// # }
     /// ```
     pub async fn remove_member(&self, project_id: &str, member_id: &str) -> crate::Result<Message> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/members/{member_id}",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/members/{member_id}"));
 
-        send_and_translate_response(self.0.client.delete(url)).await
+        send_and_translate_response(self.0.inner.client.delete(url)).await
     }
 }
@@ -1,9 +1,653 @@
 //! Manage module
 
+use crate::Deepgram;
+
+use billing::response::Balance;
+use invitations::{options::Options as InviteOptions, response::Invite};
+use keys::{
+    options::Options as KeyOptions,
+    response::{MemberAndApiKey, Message, NewApiKey},
+};
+use members::response::Member;
+use models::response::Models;
+use projects::response::Project;
+use usage::{list_requests_options::Options as UsageQuery, response::Requests};
+
 pub mod billing;
 pub mod invitations;
 pub mod keys;
 pub mod members;
+pub mod models;
 pub mod projects;
 pub mod scopes;
 pub mod usage;
+
+/// Administer a Deepgram account: projects, keys, members and usage.
+///
+/// Constructed using [`Deepgram::manage`].
+///
+/// Requests always go to Deepgram's hosted API, even when the client points
+/// at a self-hosted instance.
+///
+/// See the [Deepgram API Reference][api] for more info.
+///
+/// [api]: https://developers.deepgram.com/reference/management-api/projects/list
+#[derive(Debug, Clone)]
+pub struct Manage<'a>(&'a Deepgram);
+
+impl Deepgram {
+    /// Construct a new [`Manage`] from a [`Deepgram`].
+    pub fn manage(&self) -> Manage<'_> {
+        self.into()
+    }
+}
+
+impl<'a> From<&'a Deepgram> for Manage<'a> {
+    /// Construct a new [`Manage`] from a [`Deepgram`].
+    fn from(deepgram: &'a Deepgram) -> Self {
+        Self(deepgram)
+    }
+}
+
+impl Manage<'_> {
+    /// Get all projects the credentials have access to.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// for project in dg_client.manage().projects().await? {
+    ///     println!("{}: {}", project.project_id, project.name);
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn projects(&self) -> crate::Result<Vec<Project>> {
+        Ok(self.0.projects().list().await?.projects)
+    }
+
+    /// Get the API keys of the specified project, with the member who owns each.
+    ///
+    /// The secret part of a key is never included; see [`Manage::create_key`].
+    pub async fn keys(&self, project_id: &str) -> crate::Result<Vec<MemberAndApiKey>> {
+        Ok(self.0.keys().list(project_id).await?.api_keys)
+    }
+
+    /// Create a new API key in the specified project.
+    ///
+    /// The returned [`NewApiKey::key`] is the only time Deepgram reveals the
+    /// secret, so it must be stored by the caller.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{manage::keys::options::Options, Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// # let project_id =
+    /// #     env::var("DEEPGRAM_PROJECT_ID").expect("DEEPGRAM_PROJECT_ID environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let options = Options::builder("CI pipeline", ["usage:write"])
+    ///     .tag(["ci"])
+    ///     .time_to_live_in_seconds(3600)
+    ///     .build();
+    /// let new_key = dg_client
+    ///     .manage()
+    ///     .create_key(&project_id, &options)
+    ///     .await?;
+    /// println!("Store this secret now: {}", new_key.key);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_key(
+        &self,
+        project_id: &str,
+        options: &KeyOptions,
+    ) -> crate::Result<NewApiKey> {
+        self.0.keys().create(project_id, options).await
+    }
+
+    /// Delete the specified API key from the specified project.
+    pub async fn delete_key(&self, project_id: &str, key_id: &str) -> crate::Result<Message> {
+        self.0.keys().delete(project_id, key_id).await
+    }
+
+    /// Get one page of the requests made to the specified project, filtered
+    /// by `query`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{
+    /// #     manage::usage::list_requests_options::{Endpoint, Options},
+    /// #     Deepgram, DeepgramError,
+    /// # };
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// # let project_id =
+    /// #     env::var("DEEPGRAM_PROJECT_ID").expect("DEEPGRAM_PROJECT_ID environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let query = Options::builder()
+    ///     .start("2025-01-01")
+    ///     .end("2025-02-01")
+    ///     .endpoint(Endpoint::Listen)
+    ///     .limit(100)
+    ///     .build();
+    /// let requests = dg_client
+    ///     .manage()
+    ///     .usage_requests(&project_id, &query)
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn usage_requests(
+        &self,
+        project_id: &str,
+        query: &UsageQuery,
+    ) -> crate::Result<Requests> {
+        self.0.usage().list_requests(project_id, query).await
+    }
+
+    /// Get the outstanding balances of the specified project.
+    pub async fn balances(&self, project_id: &str) -> crate::Result<Vec<Balance>> {
+        Ok(self.0.billing().list_balance(project_id).await?.balances)
+    }
+
+    /// Get the members of the specified project.
+    pub async fn members(&self, project_id: &str) -> crate::Result<Vec<Member>> {
+        Ok(self.0.members().list_members(project_id).await?.members)
+    }
+
+    /// Remove the specified member from the specified project.
+    pub async fn remove_member(
+        &self,
+        project_id: &str,
+        member_id: &str,
+    ) -> crate::Result<members::response::Message> {
+        self.0.members().remove_member(project_id, member_id).await
+    }
+
+    /// Get the pending invitations to the specified project.
+    pub async fn invites(&self, project_id: &str) -> crate::Result<Vec<Invite>> {
+        Ok(self.0.invitations().list_invites(project_id).await?.invites)
+    }
+
+    /// Invite someone to the specified project.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{manage::invitations::options::Options, Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// # let project_id =
+    /// #     env::var("DEEPGRAM_PROJECT_ID").expect("DEEPGRAM_PROJECT_ID environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let options = Options::builder("ada@example.com", "member").build();
+    /// dg_client
+    ///     .manage()
+    ///     .send_invite(&project_id, &options)
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_invite(
+        &self,
+        project_id: &str,
+        options: &InviteOptions,
+    ) -> crate::Result<invitations::response::Message> {
+        self.0.invitations().send_invite(project_id, options).await
+    }
+
+    /// Cancel the pending invitation of `email` to the specified project.
+    pub async fn delete_invite(
+        &self,
+        project_id: &str,
+        email: &str,
+    ) -> crate::Result<invitations::response::Message> {
+        self.0.invitations().delete_invite(project_id, email).await
+    }
+
+    /// Get the public speech to text and text to speech models, for example
+    /// to offer a choice of model instead of hardcoding one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let models = dg_client.manage().models().await?;
+    /// for model in models.stt.iter().filter(|model| model.streaming) {
+    ///     println!("{} ({})", model.canonical_name, model.languages.join(", "));
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn models(&self) -> crate::Result<Models> {
+        self.0.models().list().await
+    }
+
+    /// Get the models available to the specified project, including any
+    /// custom models.
+    pub async fn project_models(&self, project_id: &str) -> crate::Result<Models> {
+        self.0.models().list_for_project(project_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use crate::{
+        manage::{
+            billing::response::BillingUnits,
+            invitations::options::Options as InviteOptions,
+            keys::options::Options as KeyOptions,
+            usage::list_requests_options::{Endpoint, Options as UsageQuery, Status},
+        },
+        mock_server, Deepgram,
+    };
+
+    const PROJECT_ID: &str = "00000000-0000-4000-8000-000000000001";
+
+    /// A client whose management requests go to `base_url`.
+    pub(crate) fn client(base_url: &str) -> Deepgram {
+        let mut dg = Deepgram::new("token").unwrap();
+        std::sync::Arc::get_mut(&mut dg.inner)
+            .unwrap()
+            .manage_base_url = base_url.parse().unwrap();
+        dg
+    }
+
+    #[tokio::test]
+    async fn projects() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async {
+                mock_server::json(
+                    200,
+                    r#"{"projects": [
+                        {"project_id": "00000000-0000-4000-8000-000000000001", "name": "Production", "company": "Acme"},
+                        {"project_id": "00000000-0000-4000-8000-000000000002", "name": "Staging"}
+                    ]}"#,
+                )
+            }
+        })
+        .await;
+
+        let projects = client(&base_url).manage().projects().await.unwrap();
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].name, "Production");
+        assert_eq!(projects[0].company.as_deref(), Some("Acme"));
+        assert_eq!(
+            projects[1].project_id.to_string(),
+            "00000000-0000-4000-8000-000000000002"
+        );
+        assert_eq!(projects[1].company, None);
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.method(), "GET");
+        assert_eq!(request.uri(), "/v1/projects");
+        assert_eq!(request.headers()["authorization"], "Token token");
+    }
+
+    #[tokio::test]
+    async fn create_key() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async {
+                mock_server::json(
+                    200,
+                    r#"{
+                        "api_key_id": "00000000-0000-4000-8000-000000000003",
+                        "key": "dg_secret",
+                        "comment": "CI pipeline",
+                        "scopes": ["usage:write"],
+                        "tags": ["ci"],
+                        "created": "2025-01-01T00:00:00.000Z",
+                        "expiration_date": "2025-01-01T01:00:00.000Z"
+                    }"#,
+                )
+            }
+        })
+        .await;
+
+        let options = KeyOptions::builder("CI pipeline", ["usage:write"])
+            .tag(["ci"])
+            .time_to_live_in_seconds(3600)
+            .build();
+        let key = client(&base_url)
+            .manage()
+            .create_key(PROJECT_ID, &options)
+            .await
+            .unwrap();
+        assert_eq!(key.key, "dg_secret");
+        assert_eq!(key.tags, Some(vec!["ci".to_string()]));
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.method(), "POST");
+        assert_eq!(
+            request.uri().path(),
+            format!("/v1/projects/{PROJECT_ID}/keys")
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(request.body()).unwrap(),
+            serde_json::json!({
+                "comment": "CI pipeline",
+                "scopes": ["usage:write"],
+                "tags": ["ci"],
+                "time_to_live_in_seconds": 3600
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn keys() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async {
+                mock_server::json(
+                    200,
+                    r#"{"api_keys": [{
+                        "member": {
+                            "member_id": "00000000-0000-4000-8000-000000000004",
+                            "email": "ada@example.com"
+                        },
+                        "api_key": {
+                            "api_key_id": "00000000-0000-4000-8000-000000000003",
+                            "comment": "CI pipeline",
+                            "scopes": ["usage:write"],
+                            "created": "2025-01-01T00:00:00.000Z"
+                        }
+                    }]}"#,
+                )
+            }
+        })
+        .await;
+
+        let keys = client(&base_url).manage().keys(PROJECT_ID).await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].member.email, "ada@example.com");
+        assert_eq!(keys[0].api_key.comment, "CI pipeline");
+        assert_eq!(keys[0].api_key.expiration_date, None);
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.method(), "GET");
+        assert_eq!(
+            request.uri().path(),
+            format!("/v1/projects/{PROJECT_ID}/keys")
+        );
+    }
+
+    #[tokio::test]
+    async fn usage_requests() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async {
+                mock_server::json(
+                    200,
+                    r#"{"page": 0, "limit": 1, "requests": [{
+                        "request_id": "00000000-0000-4000-8000-000000000005",
+                        "created": "2025-01-02T03:04:05.000Z",
+                        "path": "/v1/listen?model=nova-3",
+                        "api_key_id": "00000000-0000-4000-8000-000000000003",
+                        "response": {
+                            "details": {
+                                "usd": 0.0043,
+                                "duration": 60.0,
+                                "total_audio": 60.0,
+                                "channels": 1,
+                                "streams": 1,
+                                "models": ["00000000-0000-4000-8000-000000000006"],
+                                "method": "sync",
+                                "tags": [],
+                                "features": ["punctuate"],
+                                "config": {"model": "nova-3", "punctuate": true}
+                            },
+                            "code": 200,
+                            "completed": "2025-01-02T03:04:07.000Z"
+                        },
+                        "callback": null
+                    }]}"#,
+                )
+            }
+        })
+        .await;
+
+        let query = UsageQuery::builder()
+            .start("2025-01-01")
+            .end("2025-02-01")
+            .limit(1)
+            .status(Status::Succeeded)
+            .endpoint(Endpoint::Listen)
+            .build();
+        let requests = client(&base_url)
+            .manage()
+            .usage_requests(PROJECT_ID, &query)
+            .await
+            .unwrap();
+        let details = requests.requests[0]
+            .response
+            .as_ref()
+            .and_then(|response| response.details.as_ref())
+            .unwrap();
+        assert_eq!(details.usd, Some(0.0043));
+        assert_eq!(details.features, ["punctuate"]);
+        assert_eq!(details.config.model.as_deref(), Some("nova-3"));
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            format!(
+                "/v1/projects/{PROJECT_ID}/requests?start=2025-01-01&end=2025-02-01&limit=1&status=succeeded&endpoint=listen"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn balances() {
+        let base_url = mock_server::rest(|_| async {
+            mock_server::json(
+                200,
+                r#"{"balances": [{
+                    "balance_id": "00000000-0000-4000-8000-000000000007",
+                    "amount": 12.5,
+                    "units": "usd",
+                    "purchase_order_id": "00000000-0000-4000-8000-000000000008"
+                }]}"#,
+            )
+        })
+        .await;
+
+        let balances = client(&base_url)
+            .manage()
+            .balances(PROJECT_ID)
+            .await
+            .unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].amount, 12.5);
+        assert_eq!(balances[0].units, BillingUnits::Usd);
+    }
+
+    #[tokio::test]
+    async fn members() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async {
+                mock_server::json(
+                    200,
+                    r#"{"members": [{
+                        "member_id": "00000000-0000-4000-8000-000000000004",
+                        "first_name": "Ada",
+                        "scopes": ["owner"],
+                        "email": "ada@example.com"
+                    }]}"#,
+                )
+            }
+        })
+        .await;
+
+        let members = client(&base_url)
+            .manage()
+            .members(PROJECT_ID)
+            .await
+            .unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].first_name.as_deref(), Some("Ada"));
+        assert_eq!(members[0].last_name, None);
+        assert_eq!(members[0].scopes, ["owner"]);
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.method(), "GET");
+        assert_eq!(
+            request.uri().path(),
+            format!("/v1/projects/{PROJECT_ID}/members")
+        );
+    }
+
+    #[tokio::test]
+    async fn invites() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async {
+                mock_server::json(
+                    200,
+                    r#"{"invites": [{"email": "ada@example.com", "scope": "member"}]}"#,
+                )
+            }
+        })
+        .await;
+
+        let invites = client(&base_url)
+            .manage()
+            .invites(PROJECT_ID)
+            .await
+            .unwrap();
+        assert_eq!(invites.len(), 1);
+        assert_eq!(invites[0].email, "ada@example.com");
+        assert_eq!(invites[0].scope, "member");
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.method(), "GET");
+        assert_eq!(
+            request.uri().path(),
+            format!("/v1/projects/{PROJECT_ID}/invites")
+        );
+    }
+
+    #[tokio::test]
+    async fn send_and_delete_invite() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { mock_server::json(200, r#"{"message": "Success"}"#) }
+        })
+        .await;
+        let dg = client(&base_url);
+
+        let options = InviteOptions::builder("ada@example.com", "member").build();
+        let message = dg.manage().send_invite(PROJECT_ID, &options).await.unwrap();
+        assert_eq!(message.message, "Success");
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.method(), "POST");
+        assert_eq!(
+            request.uri().path(),
+            format!("/v1/projects/{PROJECT_ID}/invites")
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(request.body()).unwrap(),
+            serde_json::json!({"email": "ada@example.com", "scope": "member"})
+        );
+
+        dg.manage()
+            .delete_invite(PROJECT_ID, "ada+dg@example.com")
+            .await
+            .unwrap();
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.method(), "DELETE");
+        assert_eq!(
+            request.uri().path(),
+            format!("/v1/projects/{PROJECT_ID}/invites/ada+dg@example.com")
+        );
+    }
+
+    #[tokio::test]
+    async fn project_models() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async { mock_server::json(200, r#"{"stt": [], "tts": []}"#) }
+        })
+        .await;
+
+        let models = client(&base_url)
+            .manage()
+            .project_models(PROJECT_ID)
+            .await
+            .unwrap();
+        assert!(models.stt.is_empty() && models.tts.is_empty());
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.method(), "GET");
+        assert_eq!(
+            request.uri().path(),
+            format!("/v1/projects/{PROJECT_ID}/models")
+        );
+    }
+}
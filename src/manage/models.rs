@@ -0,0 +1,164 @@
+//! List the models available to a Deepgram account.
+//!
+//! See the [Deepgram API Reference][api] for more info.
+//!
+//! [api]: https://developers.deepgram.com/reference/management-api/models/list
+
+use crate::{send_and_translate_response, Deepgram};
+
+use response::Models as ModelList;
+
+pub mod response;
+
+/// List the models available to a Deepgram account.
+///
+/// Constructed using [`Deepgram::models`].
+///
+/// See the [Deepgram API Reference][api] for more info.
+///
+/// [api]: https://developers.deepgram.com/reference/management-api/models/list
+#[derive(Debug, Clone)]
+pub struct Models<'a>(&'a Deepgram);
+
+impl Deepgram {
+    /// Construct a new [`Models`] from a [`Deepgram`].
+    pub fn models(&self) -> Models<'_> {
+        self.into()
+    }
+}
+
+impl<'a> From<&'a Deepgram> for Models<'a> {
+    /// Construct a new [`Models`] from a [`Deepgram`].
+    fn from(deepgram: &'a Deepgram) -> Self {
+        Self(deepgram)
+    }
+}
+
+impl Models<'_> {
+    /// Get the public speech to text and text to speech models.
+    ///
+    /// See the [Deepgram API Reference][api] for more info.
+    ///
+    /// [api]: https://developers.deepgram.com/reference/management-api/models/list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let models = dg_client
+    ///     .models()
+    ///     .list()
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list(&self) -> crate::Result<ModelList> {
+        let url = self.0.manage_url("v1/models");
+
+        send_and_translate_response(self.0.inner.client.get(url)).await
+    }
+
+    /// Get the models available to the specified project, including any
+    /// custom models.
+    ///
+    /// See the [Deepgram API Reference][api] for more info.
+    ///
+    /// [api]: https://developers.deepgram.com/reference/management-api/projects/list-models
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{Deepgram, DeepgramError};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// # let project_id =
+    /// #     env::var("DEEPGRAM_PROJECT_ID").expect("DEEPGRAM_PROJECT_ID environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let models = dg_client
+    ///     .models()
+    ///     .list_for_project(&project_id)
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_for_project(&self, project_id: &str) -> crate::Result<ModelList> {
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/models"));
+
+        send_and_translate_response(self.0.inner.client.get(url)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::response::Models;
+
+    #[test]
+    fn deserialize_models() {
+        let models: Models = serde_json::from_str(
+            r##"{
+                "stt": [{
+                    "name": "nova-2",
+                    "canonical_name": "nova-2-general",
+                    "architecture": "nova-2",
+                    "languages": ["en", "en-us"],
+                    "version": "2024-01-09.29447",
+                    "uuid": "c2af4d7d-a3f5-4e6d-a8b0-15a2f6e8b9a1",
+                    "batch": true,
+                    "streaming": true,
+                    "formatted_output": true
+                }],
+                "tts": [{
+                    "name": "asteria",
+                    "canonical_name": "aura-asteria-en",
+                    "architecture": "aura",
+                    "languages": ["en", "en-US"],
+                    "version": "2023-10-13.0",
+                    "uuid": "5b2a1c3d-0e9f-4a8b-9c7d-6e5f4a3b2c1d",
+                    "metadata": {
+                        "accent": "American",
+                        "color": "#7800ED",
+                        "image": "https://static.deepgram.com/examples/avatars/asteria.jpg",
+                        "sample": "https://static.deepgram.com/examples/voices/asteria.wav",
+                        "tags": ["feminine"],
+                        "use_cases": ["IVR", "Advertising"]
+                    }
+                }]
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(models.stt[0].canonical_name, "nova-2-general");
+        assert_eq!(models.stt[0].languages, ["en", "en-us"]);
+        assert!(models.stt[0].streaming);
+        assert_eq!(models.tts[0].name, "asteria");
+        assert_eq!(
+            models.tts[0]
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.accent.as_deref()),
+            Some("American")
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Deepgram models API response types.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Returned by [`Models::list`](super::Models::list) and
+/// [`Models::list_for_project`](super::Models::list_for_project).
+///
+/// See the [Deepgram API Reference][api] for more info.
+///
+/// [api]: https://developers.deepgram.com/reference/management-api/models/list
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Models {
+    /// Speech to text models.
+    #[serde(default)]
+    pub stt: Vec<SttModel>,
+
+    /// Text to speech models.
+    #[serde(default)]
+    pub tts: Vec<TtsModel>,
+}
+
+/// A speech to text model.
+///
+/// See the [Deepgram API Reference][api] for more info.
+///
+/// [api]: https://developers.deepgram.com/reference/management-api/models/list
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SttModel {
+    #[allow(missing_docs)]
+    pub name: String,
+
+    /// The name to pass as the `model` option when transcribing.
+    pub canonical_name: String,
+
+    #[allow(missing_docs)]
+    pub architecture: String,
+
+    /// The languages the model can transcribe.
+    pub languages: Vec<String>,
+
+    #[allow(missing_docs)]
+    pub version: String,
+
+    #[allow(missing_docs)]
+    pub uuid: Uuid,
+
+    /// Whether the model can transcribe pre-recorded audio.
+    pub batch: bool,
+
+    /// Whether the model can transcribe live audio.
+    pub streaming: bool,
+
+    /// Whether the model supports formatting options such as `smart_format`.
+    pub formatted_output: bool,
+}
+
+/// A text to speech voice.
+///
+/// See the [Deepgram API Reference][api] for more info.
+///
+/// [api]: https://developers.deepgram.com/reference/management-api/models/list
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TtsModel {
+    #[allow(missing_docs)]
+    pub name: String,
+
+    /// The name to pass as the `model` option when generating speech.
+    pub canonical_name: String,
+
+    #[allow(missing_docs)]
+    pub architecture: String,
+
+    /// The languages the voice can speak.
+    pub languages: Vec<String>,
+
+    #[allow(missing_docs)]
+    pub version: String,
+
+    #[allow(missing_docs)]
+    pub uuid: Uuid,
+
+    #[allow(missing_docs)]
+    pub metadata: Option<TtsMetadata>,
+}
+
+/// Descriptive information about a text to speech voice.
+///
+/// See the [Deepgram API Reference][api] for more info.
+///
+/// [api]: https://developers.deepgram.com/reference/management-api/models/list
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TtsMetadata {
+    #[allow(missing_docs)]
+    pub accent: Option<String>,
+
+    #[allow(missing_docs)]
+    pub color: Option<String>,
+
+    /// URL of an avatar for the voice.
+    pub image: Option<String>,
+
+    /// URL of a sample of the voice.
+    pub sample: Option<String>,
+
+    #[allow(missing_docs)]
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    #[allow(missing_docs)]
+    #[serde(default)]
+    pub use_cases: Vec<String>,
+}
@@ -70,7 +70,7 @@ impl Projects<'_> {
     /// # }
     /// ```
     pub async fn list(&self) -> crate::Result<response::Projects> {
-        let request = self.0.client.get("https://api.deepgram.com/v1/projects");
+        let request = self.0.inner.client.get(self.0.manage_url("v1/projects"));
 
         send_and_translate_response(request).await
     }
@@ -107,9 +107,9 @@ impl Projects<'_> {
     /// # }
     /// ```
     pub async fn get(&self, project_id: &str) -> crate::Result<Project> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}");
+        let url = self.0.manage_url(&format!("v1/projects/{project_id}"));
 
-        send_and_translate_response(self.0.client.get(url)).await
+        send_and_translate_response(self.0.inner.client.get(url)).await
     }
 
     /// Update the specified project.
@@ -149,9 +149,10 @@ impl Projects<'_> {
     /// # }
     /// ```
     pub async fn update(&self, project_id: &str, options: &Options) -> crate::Result<Message> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}");
+        let url = self.0.manage_url(&format!("v1/projects/{project_id}"));
         let request = self
             .0
+            .inner
             .client
             .patch(url)
             .json(&SerializableOptions::from(options));
@@ -191,8 +192,8 @@ impl Projects<'_> {
     /// # }
     /// ```
     pub async fn delete(&self, project_id: &str) -> crate::Result<Message> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}");
-        let request = self.0.client.delete(url);
+        let url = self.0.manage_url(&format!("v1/projects/{project_id}"));
+        let request = self.0.inner.client.delete(url);
 
         send_and_translate_response(request).await
     }
@@ -80,7 +80,7 @@ impl Scopes<'_> {
             "https://api.deepgram.com/v1/projects/{project_id}/members/{member_id}/scopes "
         );
 
-        send_and_translate_response(self.0.client.get(url)).await
+        send_and_translate_response(self.0.inner.client.get(url)).await
     }
 
     /// Update the specified project scopes assigned to the specified member.
@@ -130,7 +130,7 @@ impl Scopes<'_> {
 
         let url =
             format!("https://api.deepgram.com/v1/projects/{project_id}/members/{member_id}/scopes");
-        let request = self.0.client.put(url).json(&Scope { scope });
+        let request = self.0.inner.client.put(url).json(&Scope { scope });
 
         send_and_translate_response(request).await
     }
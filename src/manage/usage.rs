@@ -78,9 +78,12 @@ impl Usage<'_> {
         project_id: &str,
         options: &list_requests_options::Options,
     ) -> crate::Result<Requests> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/requests",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/requests"));
         let request = self
             .0
+            .inner
             .client
             .get(url)
             .query(&list_requests_options::SerializableOptions::from(options));
@@ -126,10 +129,11 @@ impl Usage<'_> {
     /// # }
     /// ```
     pub async fn get_request(&self, project_id: &str, request_id: &str) -> crate::Result<Request> {
-        let url =
-            format!("https://api.deepgram.com/v1/projects/{project_id}/requests/{request_id}",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/requests/{request_id}"));
 
-        send_and_translate_response(self.0.client.get(url)).await
+        send_and_translate_response(self.0.inner.client.get(url)).await
     }
 
     /// Get a summary of usage statistics.
@@ -172,9 +176,12 @@ impl Usage<'_> {
         project_id: &str,
         options: &get_usage_options::Options,
     ) -> crate::Result<UsageSummary> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/usage");
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/usage"));
         let request = self
             .0
+            .inner
             .client
             .get(url)
             .query(&get_usage_options::SerializableOptions::from(options));
@@ -222,9 +229,12 @@ impl Usage<'_> {
         project_id: &str,
         options: &get_fields_options::Options,
     ) -> crate::Result<Fields> {
-        let url = format!("https://api.deepgram.com/v1/projects/{project_id}/usage/fields",);
+        let url = self
+            .0
+            .manage_url(&format!("v1/projects/{project_id}/usage/fields"));
         let request = self
             .0
+            .inner
             .client
             .get(url)
             .query(&get_fields_options::SerializableOptions::from(options));
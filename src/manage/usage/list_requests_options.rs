@@ -17,6 +17,8 @@ pub struct Options {
     end: Option<String>,
     limit: Option<usize>,
     status: Option<Status>,
+    endpoint: Option<Endpoint>,
+    method: Option<Method>,
 }
 
 /// Used as a parameter for [`OptionsBuilder::status`].
@@ -30,6 +32,37 @@ pub enum Status {
     Failed,
 }
 
+/// Used as a parameter for [`OptionsBuilder::endpoint`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum Endpoint {
+    /// Speech to text.
+    Listen,
+
+    /// Text intelligence.
+    Read,
+
+    /// Text to speech.
+    Speak,
+
+    /// Voice agents.
+    Agent,
+}
+
+/// Used as a parameter for [`OptionsBuilder::method`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum Method {
+    /// Requests answered in the same HTTP response.
+    Sync,
+
+    /// Requests answered with a callback.
+    Async,
+
+    /// Websocket requests.
+    Streaming,
+}
+
 /// Builds an [`Options`] object using [the Builder pattern][builder].
 ///
 /// [builder]: https://rust-unofficial.github.io/patterns/patterns/creational/builder.html
@@ -49,6 +82,12 @@ pub(crate) struct SerializableOptions<'a> {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     status: Option<&'static str>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    endpoint: Option<&'static str>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    method: Option<&'static str>,
 }
 
 impl Options {
@@ -85,6 +124,8 @@ impl OptionsBuilder {
             end: None,
             limit: None,
             status: None,
+            endpoint: None,
+            method: None,
         })
     }
 
@@ -152,6 +193,38 @@ impl OptionsBuilder {
         self
     }
 
+    /// Limits results to requests made to one kind of endpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use deepgram::manage::usage::list_requests_options::{Endpoint, Options};
+    /// #
+    /// let options1 = Options::builder()
+    ///     .endpoint(Endpoint::Speak)
+    ///     .build();
+    /// ```
+    pub fn endpoint(mut self, endpoint: Endpoint) -> Self {
+        self.0.endpoint = Some(endpoint);
+        self
+    }
+
+    /// Limits results to requests made with one method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use deepgram::manage::usage::list_requests_options::{Method, Options};
+    /// #
+    /// let options1 = Options::builder()
+    ///     .method(Method::Streaming)
+    ///     .build();
+    /// ```
+    pub fn method(mut self, method: Method) -> Self {
+        self.0.method = Some(method);
+        self
+    }
+
     /// Finish building the [`Options`] object.
     pub fn build(self) -> Options {
         self.0
@@ -172,6 +245,8 @@ impl<'a> From<&'a Options> for SerializableOptions<'a> {
             end,
             limit,
             status,
+            endpoint,
+            method,
         } = options;
 
         Self {
@@ -183,6 +258,17 @@ impl<'a> From<&'a Options> for SerializableOptions<'a> {
                 Some(Status::Failed) => Some("failed"),
                 None => None,
             },
+            endpoint: endpoint.map(|endpoint| match endpoint {
+                Endpoint::Listen => "listen",
+                Endpoint::Read => "read",
+                Endpoint::Speak => "speak",
+                Endpoint::Agent => "agent",
+            }),
+            method: method.map(|method| match method {
+                Method::Sync => "sync",
+                Method::Async => "async",
+                Method::Streaming => "streaming",
+            }),
         }
     }
 }
@@ -0,0 +1,339 @@
+//! Minimal in-process servers used to exercise the client in unit tests.
+
+#[cfg(feature = "listen")]
+pub(crate) use self::rest::chunked;
+pub(crate) use self::rest::{json, keep_alive, rest};
+
+#[cfg(feature = "listen")]
+pub(crate) use self::websocket::{connect_proxy, websocket_with_request_ids};
+#[cfg(any(feature = "listen", feature = "speak"))]
+pub(crate) use self::websocket::{websocket, REQUEST_ID};
+
+#[cfg(any(feature = "listen", feature = "speak"))]
+mod websocket {
+    use std::future::Future;
+
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::WebSocketStream;
+    use tungstenite::handshake::server::{Callback, ErrorResponse, Request, Response};
+
+    /// The request ID returned in the `dg-request-id` upgrade header.
+    pub(crate) const REQUEST_ID: &str = "00000000-0000-4000-8000-000000000000";
+
+    /// Accept websocket connections, handing each upgrade request and
+    /// connection to `handler`.
+    ///
+    /// Returns a base URL suitable for [`Deepgram::with_base_url`](crate::Deepgram::with_base_url).
+    pub(crate) async fn websocket<F, Fut>(handler: F) -> String
+    where
+        F: Fn(Request, WebSocketStream<TcpStream>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        serve(|_| REQUEST_ID.to_string(), handler).await
+    }
+
+    /// Like [`websocket`], but answering the `connection`th upgrade, counting
+    /// from zero, with `request_id(connection)` as its request ID.
+    #[cfg(feature = "listen")]
+    pub(crate) async fn websocket_with_request_ids<R, F, Fut>(request_id: R, handler: F) -> String
+    where
+        R: Fn(usize) -> String + Send + 'static,
+        F: Fn(Request, WebSocketStream<TcpStream>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        serve(request_id, handler).await
+    }
+
+    async fn serve<R, F, Fut>(request_id: R, handler: F) -> String
+    where
+        R: Fn(usize) -> String + Send + 'static,
+        F: Fn(Request, WebSocketStream<TcpStream>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let mut connection = 0;
+            while let Ok((stream, _)) = listener.accept().await {
+                let mut request = Request::default();
+                let handshake = Handshake {
+                    request: &mut request,
+                    request_id: request_id(connection),
+                };
+                connection += 1;
+                let ws_stream = tokio_tungstenite::accept_hdr_async(stream, handshake)
+                    .await
+                    .unwrap();
+                tokio::spawn(handler(request, ws_stream));
+            }
+        });
+
+        format!("http://{addr}")
+    }
+
+    /// An HTTP `CONNECT` proxy that tunnels every connection to `upstream`,
+    /// whatever target was requested.
+    ///
+    /// Returns the proxy URL and a receiver of the requested targets.
+    #[cfg(feature = "listen")]
+    pub(crate) async fn connect_proxy(
+        upstream: String,
+    ) -> (String, futures::channel::mpsc::UnboundedReceiver<String>) {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (target_tx, target_rx) = futures::channel::mpsc::unbounded();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let mut client = BufReader::new(stream);
+                let mut connect = String::new();
+                client.read_line(&mut connect).await.unwrap();
+                let mut line = String::new();
+                while client.read_line(&mut line).await.unwrap() > 2 {
+                    line.clear();
+                }
+                let target = connect.split_whitespace().nth(1).unwrap().to_owned();
+                target_tx.unbounded_send(target).unwrap();
+
+                let mut client = client.into_inner();
+                let mut server = TcpStream::connect(&upstream).await.unwrap();
+                client
+                    .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                    .await
+                    .unwrap();
+                tokio::spawn(async move {
+                    let _ = tokio::io::copy_bidirectional(&mut client, &mut server).await;
+                });
+            }
+        });
+
+        (format!("http://{addr}"), target_rx)
+    }
+
+    /// Records the upgrade request and answers it with a Deepgram request ID.
+    struct Handshake<'a> {
+        request: &'a mut Request,
+        request_id: String,
+    }
+
+    impl Callback for Handshake<'_> {
+        fn on_request(
+            self,
+            request: &Request,
+            mut response: Response,
+        ) -> std::result::Result<Response, ErrorResponse> {
+            *self.request.method_mut() = request.method().clone();
+            *self.request.uri_mut() = request.uri().clone();
+            *self.request.headers_mut() = request.headers().clone();
+            response
+                .headers_mut()
+                .insert("dg-request-id", self.request_id.parse().unwrap());
+            Ok(response)
+        }
+    }
+}
+
+mod rest {
+    use std::{
+        future::Future,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use http::{HeaderName, HeaderValue, Request, Response};
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    /// A JSON response with the given status.
+    pub(crate) fn json(status: u16, body: impl Into<String>) -> Response<Vec<u8>> {
+        Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(body.into().into_bytes())
+            .unwrap()
+    }
+
+    /// Serve HTTP/1.1 requests, one per connection, answering each with `handler`.
+    ///
+    /// Returns a base URL suitable for [`Deepgram::with_base_url`](crate::Deepgram::with_base_url).
+    pub(crate) async fn rest<F, Fut>(handler: F) -> String
+    where
+        F: Fn(Request<Vec<u8>>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response<Vec<u8>>> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handler = Arc::new(handler);
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let handler = Arc::clone(&handler);
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    let Some(request) = read_request(&mut stream).await else {
+                        return;
+                    };
+                    let response = handler(request).await;
+
+                    let stream = stream.get_mut();
+                    write_response(stream, &response, "close").await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        format!("http://{addr}")
+    }
+
+    /// Serve HTTP/1.1 requests like [`rest`], but keep each connection open
+    /// for further requests.
+    ///
+    /// Returns the base URL and the number of connections accepted so far.
+    pub(crate) async fn keep_alive<F, Fut>(handler: F) -> (String, Arc<AtomicUsize>)
+    where
+        F: Fn(Request<Vec<u8>>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response<Vec<u8>>> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handler = Arc::new(handler);
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let accepted = Arc::clone(&connections);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let handler = Arc::clone(&handler);
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    while let Some(request) = read_request(&mut stream).await {
+                        let response = handler(request).await;
+                        write_response(stream.get_mut(), &response, "keep-alive").await;
+                    }
+                });
+            }
+        });
+
+        (format!("http://{addr}"), connections)
+    }
+
+    async fn write_response<W>(stream: &mut W, response: &Response<Vec<u8>>, connection: &str)
+    where
+        W: AsyncWriteExt + Unpin,
+    {
+        let mut head = format!("HTTP/1.1 {}\r\n", response.status());
+        for (name, value) in response.headers() {
+            head.push_str(&format!("{name}: {}\r\n", value.to_str().unwrap()));
+        }
+        head.push_str(&format!(
+            "content-length: {}\r\nconnection: {connection}\r\n\r\n",
+            response.body().len()
+        ));
+        let _ = stream.write_all(head.as_bytes()).await;
+        let _ = stream.write_all(response.body()).await;
+    }
+
+    /// Serve a single request with a chunked JSON body, writing each chunk
+    /// as it arrives on `chunks` and ending the body once the sender is dropped.
+    ///
+    /// Returns a base URL suitable for [`Deepgram::with_base_url`](crate::Deepgram::with_base_url).
+    #[cfg(feature = "listen")]
+    pub(crate) async fn chunked(
+        mut chunks: futures::channel::mpsc::UnboundedReceiver<Vec<u8>>,
+    ) -> String {
+        use futures::StreamExt;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            let mut stream = BufReader::new(stream);
+            if read_request(&mut stream).await.is_none() {
+                return;
+            }
+
+            let stream = stream.get_mut();
+            let head = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                        transfer-encoding: chunked\r\nconnection: close\r\n\r\n";
+            let _ = stream.write_all(head.as_bytes()).await;
+            while let Some(chunk) = chunks.next().await {
+                let _ = stream
+                    .write_all(format!("{:x}\r\n", chunk.len()).as_bytes())
+                    .await;
+                let _ = stream.write_all(&chunk).await;
+                let _ = stream.write_all(b"\r\n").await;
+                let _ = stream.flush().await;
+            }
+            let _ = stream.write_all(b"0\r\n\r\n").await;
+            let _ = stream.shutdown().await;
+        });
+
+        format!("http://{addr}")
+    }
+
+    async fn read_request<R>(stream: &mut R) -> Option<Request<Vec<u8>>>
+    where
+        R: AsyncBufReadExt + Unpin,
+    {
+        let mut line = String::new();
+        stream.read_line(&mut line).await.ok()?;
+        let mut parts = line.split_whitespace();
+        let mut request = Request::builder()
+            .method(parts.next()?)
+            .uri(parts.next()?)
+            .body(Vec::new())
+            .ok()?;
+
+        loop {
+            let mut line = String::new();
+            stream.read_line(&mut line).await.ok()?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line.split_once(':')?;
+            request.headers_mut().append(
+                HeaderName::from_bytes(name.trim().as_bytes()).ok()?,
+                HeaderValue::from_str(value.trim()).ok()?,
+            );
+        }
+
+        let header = |name| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let mut body = Vec::new();
+        if header("transfer-encoding").as_deref() == Some("chunked") {
+            loop {
+                let mut size = String::new();
+                stream.read_line(&mut size).await.ok()?;
+                let size = usize::from_str_radix(size.trim(), 16).ok()?;
+                let mut chunk = vec![0; size + 2];
+                stream.read_exact(&mut chunk).await.ok()?;
+                if size == 0 {
+                    break;
+                }
+                body.extend_from_slice(&chunk[..size]);
+            }
+        } else if let Some(length) = header("content-length") {
+            body.resize(length.parse().ok()?, 0);
+            stream.read_exact(&mut body).await.ok()?;
+        }
+        *request.body_mut() = body;
+
+        Some(request)
+    }
+}
@@ -0,0 +1,190 @@
+//! Proxy resolution shared by the HTTP client and the websocket connector.
+
+// Only the websocket connector needs to resolve proxies itself.
+#![cfg_attr(not(any(feature = "listen", feature = "speak")), allow(dead_code))]
+
+use url::Url;
+
+/// How the client should reach Deepgram through a proxy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ProxySetting {
+    /// Dial every request through this proxy.
+    Url(Url),
+
+    /// Use `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY`.
+    Env,
+}
+
+impl ProxySetting {
+    /// The proxy to use when connecting to `target`, if any.
+    pub(crate) fn resolve(&self, target: &Url) -> Option<Url> {
+        match self {
+            ProxySetting::Url(url) => Some(url.clone()),
+            ProxySetting::Env => from_env(target, |name| std::env::var(name).ok()),
+        }
+    }
+}
+
+/// Pick the proxy for `target` from environment variables, following the
+/// conventions used by curl and reqwest.
+fn from_env(target: &Url, var: impl Fn(&str) -> Option<String>) -> Option<Url> {
+    // Lowercase variants are the more common convention, so they win.
+    let var = |name: &str| {
+        var(&name.to_lowercase())
+            .or_else(|| var(name))
+            .filter(|value| !value.is_empty())
+    };
+
+    let host = target.host_str()?;
+    if let Some(no_proxy) = var("NO_PROXY") {
+        if bypasses(&no_proxy, host) {
+            return None;
+        }
+    }
+
+    let proxy = match target.scheme() {
+        "https" | "wss" => var("HTTPS_PROXY"),
+        _ => var("HTTP_PROXY"),
+    }
+    .or_else(|| var("ALL_PROXY"))?;
+
+    // A bare `host:port` is taken to be an HTTP proxy.
+    Url::parse(&proxy)
+        .ok()
+        .filter(|url| url.has_host())
+        .or_else(|| Url::parse(&format!("http://{proxy}")).ok())
+}
+
+/// Whether `host` matches an entry of a `NO_PROXY` list.
+fn bypasses(no_proxy: &str, host: &str) -> bool {
+    no_proxy
+        .split(',')
+        .map(|entry| entry.trim().trim_start_matches('.'))
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            entry == "*"
+                || host.eq_ignore_ascii_case(entry)
+                || host
+                    .to_ascii_lowercase()
+                    .ends_with(&format!(".{}", entry.to_ascii_lowercase()))
+        })
+}
+
+#[cfg(any(feature = "listen", feature = "speak"))]
+pub(crate) use self::tunnel::tunnel;
+
+#[cfg(any(feature = "listen", feature = "speak"))]
+mod tunnel {
+    use anyhow::anyhow;
+    use base64::Engine;
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpStream,
+    };
+    use url::Url;
+
+    use crate::{DeepgramError, Result};
+
+    /// Open a TCP connection to `host:port` through an HTTP `CONNECT` proxy.
+    pub(crate) async fn tunnel(proxy: &Url, host: &str, port: u16) -> Result<TcpStream> {
+        let proxy_host = proxy.host_str().ok_or(DeepgramError::InvalidUrl)?;
+        let proxy_port = proxy
+            .port_or_known_default()
+            .ok_or(DeepgramError::InvalidUrl)?;
+        let mut stream = TcpStream::connect((proxy_host, proxy_port)).await?;
+
+        let mut connect = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+        if !proxy.username().is_empty() {
+            let credentials = format!(
+                "{}:{}",
+                proxy.username(),
+                proxy.password().unwrap_or_default()
+            );
+            connect.push_str(&format!(
+                "Proxy-Authorization: Basic {}\r\n",
+                base64::engine::general_purpose::STANDARD.encode(credentials)
+            ));
+        }
+        connect.push_str("\r\n");
+        stream.write_all(connect.as_bytes()).await?;
+
+        // Read the proxy's response head, leaving the tunnel untouched afterwards.
+        let mut reader = BufReader::new(&mut stream);
+        let mut status = String::new();
+        reader.read_line(&mut status).await?;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await? == 0 || line == "\r\n" {
+                break;
+            }
+        }
+        if !reader.buffer().is_empty() {
+            return Err(DeepgramError::UnexpectedServerResponse(anyhow!(
+                "Proxy sent data before the tunnel was established"
+            )));
+        }
+
+        match status.split_whitespace().nth(1) {
+            Some(code) if code.starts_with('2') => Ok(stream),
+            _ => Err(DeepgramError::UnexpectedServerResponse(anyhow!(
+                "Proxy refused to open a tunnel: {}",
+                status.trim_end()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use url::Url;
+
+    use super::from_env;
+
+    fn resolve(target: &str, vars: &[(&str, &str)]) -> Option<String> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        from_env(&Url::parse(target).unwrap(), |name| {
+            vars.get(name).map(|value| value.to_string())
+        })
+        .map(|url| url.to_string())
+    }
+
+    #[test]
+    fn picks_proxy_by_scheme() {
+        let vars = [
+            ("HTTPS_PROXY", "http://secure:3128"),
+            ("http_proxy", "plain:8080"),
+        ];
+        assert_eq!(
+            resolve("wss://api.deepgram.com", &vars).as_deref(),
+            Some("http://secure:3128/")
+        );
+        assert_eq!(
+            resolve("http://localhost:8080", &vars).as_deref(),
+            Some("http://plain:8080/")
+        );
+        assert_eq!(resolve("https://api.deepgram.com", &[]), None);
+    }
+
+    #[test]
+    fn respects_no_proxy() {
+        let vars = [
+            ("ALL_PROXY", "http://proxy:3128"),
+            ("NO_PROXY", "localhost, .internal"),
+        ];
+        assert_eq!(resolve("http://localhost:8080", &vars), None);
+        assert_eq!(resolve("http://deepgram.internal", &vars), None);
+        assert_eq!(
+            resolve("https://api.deepgram.com", &vars).as_deref(),
+            Some("http://proxy:3128/")
+        );
+        assert_eq!(
+            resolve(
+                "https://api.deepgram.com",
+                &[("ALL_PROXY", "proxy:1"), ("no_proxy", "*")]
+            ),
+            None
+        );
+    }
+}
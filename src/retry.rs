@@ -0,0 +1,310 @@
+//! Retrying HTTP requests that failed for transient reasons.
+
+// Only pre-recorded transcription retries its requests.
+#![cfg_attr(not(feature = "listen"), allow(dead_code))]
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use reqwest::{header::RETRY_AFTER, RequestBuilder, Response, StatusCode};
+
+/// Controls how requests are retried after a transient failure.
+///
+/// A request is retried when Deepgram answers with `429 Too Many Requests`,
+/// `500`, `502`, `503` or `504`, or when the connection could not be
+/// established. Any other status, such as `400 Bad Request` or
+/// `401 Unauthorized`, fails immediately.
+///
+/// Attempts are spaced by a jittered exponential backoff starting at the
+/// initial backoff and capped at [`RetryPolicy::max_backoff`]. A
+/// `Retry-After` header sent by Deepgram takes precedence over the backoff.
+///
+/// Requests whose body is a stream cannot be replayed, so they are only ever
+/// sent once.
+///
+/// Used with [`DeepgramBuilder::retry`](crate::DeepgramBuilder::retry), or
+/// for a single request with
+/// [`PrerecordedBuilder::retry`](crate::listen::rest::PrerecordedBuilder::retry).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Retry up to `max_retries` times, waiting about `initial_backoff`
+    /// before the first retry and doubling the wait after each failure.
+    pub fn new(max_retries: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    /// Set the longest wait between two attempts. Defaults to 30 seconds.
+    ///
+    /// This also caps the wait requested by a `Retry-After` header.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// The wait before `retry`, counting from one, between half and all of
+    /// the exponential backoff.
+    fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        let jitter = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        backoff.mul_f64(0.5 + jitter / 2.0)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(500))
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// The wait requested by a `Retry-After` header given in seconds.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    seconds.trim().parse().ok().map(Duration::from_secs)
+}
+
+/// Send the request, retrying as allowed by `policy`.
+///
+/// The last response is returned whatever its status, so the caller still
+/// gets to report Deepgram's error.
+pub(crate) async fn send(
+    request_builder: RequestBuilder,
+    policy: Option<&RetryPolicy>,
+) -> reqwest::Result<Response> {
+    let Some(policy) = policy else {
+        return request_builder.send().await;
+    };
+
+    let mut retry = 0;
+    let mut request_builder = request_builder;
+    loop {
+        let next = match request_builder.try_clone() {
+            Some(next) if retry < policy.max_retries => next,
+            _ => return request_builder.send().await,
+        };
+        retry += 1;
+
+        let wait = match request_builder.send().await {
+            Ok(response) if is_retryable(response.status()) => retry_after(&response)
+                .map(|wait| wait.min(policy.max_backoff))
+                .unwrap_or_else(|| policy.backoff(retry)),
+            Err(err) if err.is_connect() || err.is_timeout() => policy.backoff(retry),
+            result => return result,
+        };
+        tokio::time::sleep(wait).await;
+        request_builder = next;
+    }
+}
+
+#[cfg(all(test, feature = "listen"))]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use super::RetryPolicy;
+    use crate::{
+        common::{audio_source::AudioSource, options::Options},
+        mock_server, Deepgram, DeepgramError,
+    };
+
+    const RESPONSE: &str = r#"{
+        "metadata": {
+            "request_id": "00000000-0000-4000-8000-000000000000",
+            "transaction_key": "deprecated",
+            "sha256": "6d3c8b7a",
+            "created": "2025-01-01T00:00:00.000Z",
+            "duration": 1.5,
+            "channels": 1
+        },
+        "results": {"channels": []}
+    }"#;
+
+    /// A server failing the first `failures` requests with `status`, counting attempts.
+    async fn flaky(status: u16, failures: usize) -> (String, Arc<AtomicUsize>) {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let base_url = mock_server::rest({
+            let attempts = Arc::clone(&attempts);
+            move |_| {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt < failures {
+                        mock_server::json(status, r#"{"err_code": "FLAKY"}"#)
+                    } else {
+                        mock_server::json(200, RESPONSE)
+                    }
+                }
+            }
+        })
+        .await;
+        (base_url, attempts)
+    }
+
+    fn client(base_url: &str, policy: RetryPolicy) -> Deepgram {
+        Deepgram::builder()
+            .base_url(base_url.parse().unwrap())
+            .retry(policy)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn retries_unavailable() {
+        let (base_url, attempts) = flaky(503, 2).await;
+        let dg = client(&base_url, RetryPolicy::new(3, Duration::from_millis(1)));
+
+        dg.transcription()
+            .prerecorded_buffer(vec![0; 320], "audio/wav", &Options::default())
+            .await
+            .unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let (base_url, attempts) = flaky(503, 10).await;
+        let dg = client(&base_url, RetryPolicy::new(2, Duration::from_millis(1)));
+
+        let err = dg
+            .transcription()
+            .prerecorded_url(
+                "https://example.com/audio.wav".parse().unwrap(),
+                &Options::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeepgramError::DeepgramApiError { .. }));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_bad_request() {
+        let (base_url, attempts) = flaky(400, 1).await;
+        let dg = client(&base_url, RetryPolicy::new(3, Duration::from_millis(1)));
+
+        assert!(dg
+            .transcription()
+            .prerecorded_buffer(vec![0; 320], "audio/wav", &Options::default())
+            .await
+            .is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_streamed_body() {
+        let (base_url, attempts) = flaky(503, 1).await;
+        let dg = client(&base_url, RetryPolicy::new(3, Duration::from_millis(1)));
+
+        let audio =
+            futures::stream::iter([Ok::<_, std::io::Error>(bytes::Bytes::from(vec![0; 320]))]);
+        assert!(dg
+            .transcription()
+            .prerecorded_stream(audio, "audio/wav", &Options::default())
+            .await
+            .is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn request_policy_overrides_client() {
+        let source = || AudioSource::from_buffer_with_mime_type(vec![0; 320], "audio/wav");
+
+        // Retried although the client doesn't retry.
+        let (base_url, attempts) = flaky(503, 2).await;
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        dg.transcription()
+            .prerecorded_request(source(), &Options::default())
+            .retry(RetryPolicy::new(3, Duration::from_millis(1)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        // Sent once although the client retries.
+        let (base_url, attempts) = flaky(503, 2).await;
+        let dg = client(&base_url, RetryPolicy::new(3, Duration::from_millis(1)));
+        let err = dg
+            .transcription()
+            .prerecorded_request(source(), &Options::default())
+            .retry(RetryPolicy::new(0, Duration::ZERO))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeepgramError::DeepgramApiError { .. }));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn honours_retry_after() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let base_url = mock_server::rest({
+            let attempts = Arc::clone(&attempts);
+            move |_| {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        let mut response = mock_server::json(429, "{}");
+                        response
+                            .headers_mut()
+                            .insert("retry-after", "1".parse().unwrap());
+                        response
+                    } else {
+                        mock_server::json(200, RESPONSE)
+                    }
+                }
+            }
+        })
+        .await;
+        // The backoff alone would retry almost immediately.
+        let dg = client(&base_url, RetryPolicy::new(1, Duration::from_millis(1)));
+
+        let start = std::time::Instant::now();
+        dg.transcription()
+            .prerecorded_buffer(vec![0; 320], "audio/wav", &Options::default())
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn backoff_is_jittered_and_capped() {
+        let policy =
+            RetryPolicy::new(10, Duration::from_millis(100)).max_backoff(Duration::from_secs(1));
+        for _ in 0..20 {
+            let first = policy.backoff(1);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+            assert!(policy.backoff(10) <= Duration::from_secs(1));
+        }
+    }
+}
@@ -31,6 +31,7 @@ impl Speak<'_> {
 
         let request_builder = self
             .0
+            .inner
             .client
             .post(self.speak_url())
             .query(&SerializableOptions(options))
@@ -47,15 +48,10 @@ impl Speak<'_> {
     ) -> Result<(), DeepgramError> {
         let mut response = request_builder.send().await?;
 
-        if let Err(err) = response.error_for_status_ref() {
-            let status = response.status();
-            let error_text = response.text().await?;
-            eprintln!("Failed to generate speech: {status}");
-            eprintln!("Error details: {error_text}");
-            return Err(DeepgramError::DeepgramApiError {
-                body: error_text,
-                err,
-            });
+        if !response.status().is_success() {
+            let err = DeepgramError::from_response(response).await;
+            eprintln!("Failed to generate speech: {err}");
+            return Err(err);
         }
 
         // Create the output file
@@ -86,6 +82,7 @@ impl Speak<'_> {
 
         let request_builder = self
             .0
+            .inner
             .client
             .post(self.speak_url())
             .query(&SerializableOptions(options))
@@ -100,15 +97,10 @@ impl Speak<'_> {
     ) -> Result<impl Stream<Item = Bytes>, DeepgramError> {
         let response = request_builder.send().await?;
 
-        if let Err(err) = response.error_for_status_ref() {
-            let status = response.status();
-            let error_text = response.text().await?;
-            eprintln!("Failed to generate speech: {status}");
-            eprintln!("Error details: {error_text}");
-            return Err(DeepgramError::DeepgramApiError {
-                body: error_text,
-                err,
-            });
+        if !response.status().is_success() {
+            let err = DeepgramError::from_response(response).await;
+            eprintln!("Failed to generate speech: {err}");
+            return Err(err);
         }
 
         let (tx, rx) = mpsc::channel(1024);
@@ -135,14 +127,87 @@ impl Speak<'_> {
         Ok(rx_stream)
     }
 
+    /// Sends a request to Deepgram to synthesize speech from `text`,
+    /// streaming the audio back as it is generated.
+    ///
+    /// Unlike [`Speak::speak_to_stream`], errors that occur while the audio
+    /// is being received are yielded by the stream rather than dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::env;
+    /// #
+    /// # use deepgram::{
+    /// #     speak::options::{Container, Encoding, Model, Options},
+    /// #     Deepgram, DeepgramError,
+    /// # };
+    /// # use futures::StreamExt;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeepgramError> {
+    /// # let deepgram_api_key =
+    /// #     env::var("DEEPGRAM_API_KEY").expect("DEEPGRAM_API_KEY environmental variable");
+    /// #
+    /// let dg_client = Deepgram::new(&deepgram_api_key)?;
+    ///
+    /// let options = Options::builder()
+    ///     .model(Model::AuraAsteriaEn)
+    ///     .encoding(Encoding::Linear16)
+    ///     .sample_rate(16000)
+    ///     .container(Container::Wav)
+    ///     .build();
+    ///
+    /// let mut audio = dg_client
+    ///     .text_to_speech()
+    ///     .synthesize("Hello, world!", &options)
+    ///     .await?;
+    /// while let Some(chunk) = audio.next().await {
+    ///     let chunk = chunk?;
+    ///     // Play or save the chunk.
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn synthesize(
+        &self,
+        text: &str,
+        options: &Options,
+    ) -> Result<impl Stream<Item = Result<Bytes, DeepgramError>>, DeepgramError> {
+        let response = self
+            .0
+            .inner
+            .client
+            .post(self.speak_url())
+            .query(&SerializableOptions(options))
+            .json(&serde_json::json!({ "text": text }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(DeepgramError::from_response(response).await);
+        }
+
+        Ok(response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(DeepgramError::from)))
+    }
+
     fn speak_url(&self) -> Url {
-        self.0.base_url.join(DEEPGRAM_API_URL_SPEAK).unwrap()
+        self.0.inner.base_url.join(DEEPGRAM_API_URL_SPEAK).unwrap()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Deepgram;
+    use futures::StreamExt;
+
+    use crate::{
+        mock_server,
+        speak::options::{Container, Encoding, Model, Options},
+        Deepgram,
+    };
 
     #[test]
     fn listen_url() {
@@ -152,4 +217,61 @@ mod tests {
             "https://api.deepgram.com/v1/speak"
         );
     }
+
+    #[test]
+    fn container_query_values() {
+        for (container, expected) in [
+            (Container::Wav, "container=wav"),
+            (Container::Ogg, "container=ogg"),
+            (Container::None, "container=none"),
+            (
+                Container::CustomContainer("mp4".to_string()),
+                "container=mp4",
+            ),
+        ] {
+            let options = Options::builder().container(container).build();
+            assert_eq!(options.urlencoded().unwrap(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn synthesize() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::rest(move |request| {
+            request_tx.unbounded_send(request).unwrap();
+            async {
+                http::Response::builder()
+                    .header("content-type", "audio/wav")
+                    .body(b"RIFF fake audio".to_vec())
+                    .unwrap()
+            }
+        })
+        .await;
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let options = Options::builder()
+            .model(Model::AuraAsteriaEn)
+            .encoding(Encoding::Linear16)
+            .sample_rate(16000)
+            .container(Container::Wav)
+            .build();
+        let audio = dg
+            .text_to_speech()
+            .synthesize("Hello", &options)
+            .await
+            .unwrap();
+        let chunks: Vec<_> = audio.map(Result::unwrap).collect().await;
+        assert_eq!(chunks.concat(), b"RIFF fake audio");
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(request.method(), "POST");
+        assert_eq!(
+            request.uri(),
+            "/v1/speak?model=aura-asteria-en&encoding=linear16&sample_rate=16000&container=wav"
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(request.body()).unwrap(),
+            serde_json::json!({"text": "Hello"})
+        );
+    }
 }
@@ -0,0 +1,476 @@
+//! Types used for streaming text to speech over a websocket.
+//!
+//! Text is sent incrementally and audio is returned as soon as it is
+//! generated, which keeps latency low for voice agents.
+//!
+//! See the [Deepgram API Reference][api] for more info.
+//!
+//! [api]: https://developers.deepgram.com/reference/text-to-speech-api/speak-streaming
+
+use bytes::Bytes;
+use futures::{
+    channel::mpsc::{self, Receiver, Sender},
+    select_biased,
+    stream::StreamExt,
+    SinkExt,
+};
+use http::Request;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream};
+use tungstenite::{handshake::client, Utf8Bytes};
+use url::Url;
+use uuid::Uuid;
+
+use super::options::{Options, SerializableOptions};
+use crate::{
+    websocket::{add_custom_headers, connect, request_id},
+    Deepgram, DeepgramError, Result, Speak,
+};
+
+static SPEAK_STREAM_URL_PATH: &str = "v1/speak";
+
+/// Configures a streaming text to speech request.
+///
+/// Constructed using [`Speak::stream_request`].
+#[derive(Clone, Debug)]
+pub struct SpeakWebsocketBuilder<'a> {
+    deepgram: &'a Deepgram,
+    options: Options,
+    stream_url: Url,
+}
+
+impl Speak<'_> {
+    /// Begin to configure a streaming text to speech request.
+    ///
+    /// Once configured, the connection is opened with
+    /// [`SpeakWebsocketBuilder::handle`].
+    ///
+    /// ```
+    /// use deepgram::{
+    ///     speak::options::{Encoding, Model, Options},
+    ///     Deepgram,
+    /// };
+    ///
+    /// let dg = Deepgram::new("apikey12345").unwrap();
+    /// let options = Options::builder()
+    ///     .model(Model::AuraAsteriaEn)
+    ///     .encoding(Encoding::Linear16)
+    ///     .sample_rate(16000)
+    ///     .build();
+    /// let speak = dg.text_to_speech();
+    /// let builder = speak.stream_request(&options);
+    ///
+    /// assert_eq!(
+    ///     &builder.urlencoded().unwrap(),
+    ///     "model=aura-asteria-en&encoding=linear16&sample_rate=16000"
+    /// );
+    /// ```
+    pub fn stream_request(&self, options: &Options) -> SpeakWebsocketBuilder<'_> {
+        SpeakWebsocketBuilder {
+            deepgram: self.0,
+            options: options.clone(),
+            stream_url: self.speak_stream_url(),
+        }
+    }
+
+    fn speak_stream_url(&self) -> Url {
+        let mut url =
+            self.0.inner.base_url.join(SPEAK_STREAM_URL_PATH).expect(
+                "base_url is checked to be a valid base_url when constructing Deepgram client",
+            );
+
+        match url.scheme() {
+            "http" | "ws" => url
+                .set_scheme("ws")
+                .expect("a valid conversion according to the .set_scheme docs"),
+            "https" | "wss" => url
+                .set_scheme("wss")
+                .expect("a valid conversion according to the .set_scheme docs"),
+            _ => unreachable!(
+                "base_url is validated to have a scheme of http, https, ws, or wss when constructing Deepgram client"
+            ),
+        }
+        url
+    }
+}
+
+impl SpeakWebsocketBuilder<'_> {
+    /// Return the options in urlencoded format. If serialization would
+    /// fail, this will also return an error.
+    ///
+    /// This is intended primarily to help with debugging API requests.
+    pub fn urlencoded(&self) -> std::result::Result<String, serde_urlencoded::ser::Error> {
+        Ok(self.as_url()?.query().unwrap_or_default().to_string())
+    }
+
+    fn as_url(&self) -> std::result::Result<Url, serde_urlencoded::ser::Error> {
+        let mut url = self.stream_url.clone();
+        let query = serde_urlencoded::to_string(SerializableOptions(&self.options))?;
+        url.set_query(Some(query.as_str()).filter(|query| !query.is_empty()));
+        Ok(url)
+    }
+
+    /// Open the websocket connection.
+    pub async fn handle(self) -> Result<SpeakWebsocketHandle> {
+        SpeakWebsocketHandle::new(self).await
+    }
+}
+
+/// A message received from the streaming text to speech API.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type")]
+#[non_exhaustive]
+pub enum SpeakResponse {
+    /// A chunk of generated audio.
+    #[serde(skip)]
+    Audio(Bytes),
+
+    /// Information about the connection, sent when it is opened.
+    Metadata {
+        /// The Deepgram request ID.
+        request_id: Uuid,
+        /// The name of the voice model.
+        model_name: String,
+        /// The version of the voice model.
+        model_version: String,
+        /// The UUID of the voice model.
+        model_uuid: Uuid,
+    },
+
+    /// All audio for the text sent before a [`SpeakWebsocketHandle::flush`]
+    /// has been delivered.
+    Flushed {
+        /// The number of the flush being acknowledged, starting from zero.
+        sequence_id: u32,
+    },
+
+    /// Text and audio buffered before a [`SpeakWebsocketHandle::clear`] have
+    /// been discarded.
+    Cleared {
+        /// The number of the clear being acknowledged, starting from zero.
+        sequence_id: u32,
+    },
+
+    /// A non-fatal problem with the request.
+    Warning {
+        /// A description of the problem.
+        description: String,
+        /// A machine-readable code for the problem.
+        code: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type")]
+enum ControlMessage {
+    Speak { text: String },
+    Flush,
+    Clear,
+    Close,
+}
+
+/// A low level interface to the Deepgram streaming text to speech API.
+///
+/// Constructed using [`SpeakWebsocketBuilder::handle`].
+#[derive(Debug)]
+pub struct SpeakWebsocketHandle {
+    message_tx: Sender<ControlMessage>,
+    response_rx: Receiver<Result<SpeakResponse>>,
+    request_id: Uuid,
+}
+
+impl SpeakWebsocketHandle {
+    async fn new(builder: SpeakWebsocketBuilder<'_>) -> Result<SpeakWebsocketHandle> {
+        let url = builder.as_url()?;
+        let host = url.host_str().ok_or(DeepgramError::InvalidUrl)?;
+        let host = match url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+
+        let request = {
+            let http_builder = Request::builder()
+                .method("GET")
+                .uri(url.to_string())
+                .header("sec-websocket-key", client::generate_key())
+                .header("host", host)
+                .header("connection", "upgrade")
+                .header("upgrade", "websocket")
+                .header("sec-websocket-version", "13")
+                .header("user-agent", &builder.deepgram.inner.user_agent);
+
+            let request_builder = if let Some(auth) = &builder.deepgram.inner.auth {
+                http_builder.header("authorization", auth.header_value())
+            } else {
+                http_builder
+            };
+            let mut request = request_builder.body(())?;
+            add_custom_headers(request.headers_mut(), &builder.deepgram.inner.headers);
+            request
+        };
+
+        let proxy = builder
+            .deepgram
+            .inner
+            .proxy
+            .as_ref()
+            .and_then(|proxy| proxy.resolve(&url));
+        let (ws_stream, upgrade_response) = connect(
+            request,
+            proxy.as_ref(),
+            None,
+            builder.deepgram.inner.connect_timeout,
+        )
+        .await?;
+
+        let request_id = request_id(&upgrade_response)?;
+
+        let (message_tx, message_rx) = mpsc::channel(256);
+        let (response_tx, response_rx) = mpsc::channel(256);
+
+        tokio::task::spawn(run_worker(ws_stream, message_rx, response_tx));
+
+        Ok(SpeakWebsocketHandle {
+            message_tx,
+            response_rx,
+            request_id,
+        })
+    }
+
+    /// Send text to be spoken.
+    ///
+    /// Deepgram buffers the text until it has enough to generate natural
+    /// sounding audio; call [`SpeakWebsocketHandle::flush`] to have it
+    /// generate audio for everything sent so far.
+    pub async fn send_text(&mut self, text: &str) -> Result<()> {
+        self.send_control_message(ControlMessage::Speak {
+            text: text.to_owned(),
+        })
+        .await
+    }
+
+    /// Generate audio for all text sent so far.
+    ///
+    /// A [`SpeakResponse::Flushed`] is received once the audio has been sent.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.send_control_message(ControlMessage::Flush).await
+    }
+
+    /// Discard any buffered text and audio that has not been sent yet, such
+    /// as when the listener interrupts.
+    ///
+    /// A [`SpeakResponse::Cleared`] is received once the buffers are empty.
+    pub async fn clear(&mut self) -> Result<()> {
+        self.send_control_message(ControlMessage::Clear).await
+    }
+
+    /// Ask Deepgram to send any remaining audio and close the connection.
+    ///
+    /// No more text should be sent after this is called. Remaining audio can
+    /// still be read with [`SpeakWebsocketHandle::receive`].
+    pub async fn close_stream(&mut self) -> Result<()> {
+        if !self.message_tx.is_closed() {
+            self.send_control_message(ControlMessage::Close).await?;
+            self.message_tx.close_channel();
+        }
+        Ok(())
+    }
+
+    async fn send_control_message(&mut self, message: ControlMessage) -> Result<()> {
+        self.message_tx
+            .send(message)
+            .await
+            .map_err(|err| DeepgramError::InternalClientError(err.into()))
+    }
+
+    /// Receive the next chunk of audio or control event.
+    ///
+    /// Returns `None` once the connection has closed.
+    pub async fn receive(&mut self) -> Option<Result<SpeakResponse>> {
+        self.response_rx.next().await
+    }
+
+    /// Returns the Deepgram request ID for the text to speech request.
+    ///
+    /// It is the nil UUID if the server did not send a request ID.
+    pub fn request_id(&self) -> Uuid {
+        self.request_id
+    }
+}
+
+fn control_frame(message: &ControlMessage) -> Message {
+    Message::Text(Utf8Bytes::from(
+        serde_json::to_string(message).unwrap_or_default(),
+    ))
+}
+
+async fn run_worker(
+    ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    mut message_rx: Receiver<ControlMessage>,
+    mut response_tx: Sender<Result<SpeakResponse>>,
+) {
+    let (mut ws_stream_send, ws_stream_recv) = ws_stream.split();
+    let mut ws_stream_recv = ws_stream_recv.fuse();
+    let mut is_open = true;
+    loop {
+        let response = select_biased! {
+            response = ws_stream_recv.next() => match response {
+                Some(Ok(Message::Binary(audio))) => Ok(SpeakResponse::Audio(audio)),
+                Some(Ok(Message::Text(text))) => crate::websocket::parse_message(&text),
+                Some(Ok(Message::Ping(value))) => {
+                    // We don't really care if the server receives the pong.
+                    let _ = ws_stream_send.send(Message::Pong(value)).await;
+                    continue;
+                }
+                Some(Ok(Message::Close(Some(closeframe)))) if closeframe.code != 1000.into() => {
+                    Err(DeepgramError::WebsocketClose {
+                        code: closeframe.code.into(),
+                        reason: closeframe.reason.to_string(),
+                    })
+                }
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(Message::Pong(_) | Message::Frame(_))) => continue,
+                Some(Err(err)) => Err(err.into()),
+            },
+            message = message_rx.next() => {
+                if is_open {
+                    let message = message.unwrap_or(ControlMessage::Close);
+                    is_open = message != ControlMessage::Close;
+                    match ws_stream_send.send(control_frame(&message)).await {
+                        Ok(()) => continue,
+                        Err(err) => Err(err.into()),
+                    }
+                } else {
+                    continue;
+                }
+            }
+        };
+        if response_tx.send(response).await.is_err() {
+            // Responses are no longer being received; close the stream.
+            break;
+        }
+    }
+    if is_open {
+        // If the connection is already gone, there's nothing to be done about it now.
+        let _ = ws_stream_send
+            .send(control_frame(&ControlMessage::Close))
+            .await;
+    }
+    response_tx.close_channel();
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::protocol::Message;
+
+    use super::{ControlMessage, SpeakResponse};
+    use crate::{
+        mock_server,
+        speak::options::{Encoding, Model, Options},
+        Deepgram,
+    };
+
+    #[test]
+    fn test_speak_stream_url() {
+        let dg = Deepgram::new("token").unwrap();
+        assert_eq!(
+            dg.text_to_speech().speak_stream_url().to_string(),
+            "wss://api.deepgram.com/v1/speak",
+        );
+    }
+
+    #[test]
+    fn control_message_format() {
+        assert_eq!(
+            serde_json::to_string(&ControlMessage::Speak {
+                text: "Hello".to_string()
+            })
+            .unwrap(),
+            r#"{"type":"Speak","text":"Hello"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&ControlMessage::Flush).unwrap(),
+            r#"{"type":"Flush"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn send_flush_receive() {
+        let (request_tx, mut request_rx) = futures::channel::mpsc::unbounded();
+        let base_url = mock_server::websocket(move |request, mut ws| {
+            request_tx.unbounded_send(request).unwrap();
+            async move {
+                let mut text = String::new();
+                while let Some(Ok(Message::Text(message))) = ws.next().await {
+                    let message: serde_json::Value = serde_json::from_str(&message).unwrap();
+                    match message["type"].as_str().unwrap() {
+                        "Speak" => text.push_str(message["text"].as_str().unwrap()),
+                        "Flush" => {
+                            ws.send(Message::binary(std::mem::take(&mut text).into_bytes()))
+                                .await
+                                .unwrap();
+                            ws.send(Message::text(r#"{"type":"Flushed","sequence_id":0}"#))
+                                .await
+                                .unwrap();
+                        }
+                        "Clear" => {
+                            text.clear();
+                            ws.send(Message::text(r#"{"type":"Cleared","sequence_id":1}"#))
+                                .await
+                                .unwrap();
+                        }
+                        _ => {
+                            ws.close(None).await.unwrap();
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+        .await;
+
+        let dg = Deepgram::with_base_url(base_url.as_str()).unwrap();
+        let options = Options::builder()
+            .model(Model::AuraAsteriaEn)
+            .encoding(Encoding::Linear16)
+            .sample_rate(16000)
+            .build();
+        let mut handle = dg
+            .text_to_speech()
+            .stream_request(&options)
+            .handle()
+            .await
+            .unwrap();
+        assert_eq!(handle.request_id().to_string(), mock_server::REQUEST_ID);
+
+        handle.send_text("Hello, ").await.unwrap();
+        handle.send_text("world!").await.unwrap();
+        handle.flush().await.unwrap();
+        assert_eq!(
+            handle.receive().await.unwrap().unwrap(),
+            SpeakResponse::Audio("Hello, world!".into())
+        );
+        assert_eq!(
+            handle.receive().await.unwrap().unwrap(),
+            SpeakResponse::Flushed { sequence_id: 0 }
+        );
+
+        handle.send_text("Never mind").await.unwrap();
+        handle.clear().await.unwrap();
+        assert_eq!(
+            handle.receive().await.unwrap().unwrap(),
+            SpeakResponse::Cleared { sequence_id: 1 }
+        );
+
+        handle.close_stream().await.unwrap();
+        assert!(handle.receive().await.is_none());
+
+        let request = request_rx.next().await.unwrap();
+        assert_eq!(
+            request.uri(),
+            "/v1/speak?model=aura-asteria-en&encoding=linear16&sample_rate=16000"
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Scripted stand-ins for live transcription, to test code built on the SDK
+//! without reaching Deepgram.
+//!
+//! A [`MockDeepgram`] holds the [`StreamResponse`]s a test expects Deepgram to
+//! send. Each [`MockStreamHandle`] it creates yields those responses in order
+//! through the same methods as a [`WebsocketHandle`], and records the audio
+//! sent to it for assertions.
+//!
+//! ```
+//! use deepgram::{
+//!     common::stream_response::StreamResponse,
+//!     test_util::{MockDeepgram, MockStreamHandle},
+//! };
+//!
+//! // The code under test: send the audio, then count the final transcripts.
+//! async fn count_finals(handle: &mut MockStreamHandle, audio: Vec<u8>) -> usize {
+//!     handle.send_data(audio).await.unwrap();
+//!     handle.close_stream().await.unwrap();
+//!
+//!     let mut finals = 0;
+//!     while let Some(response) = handle.receive().await {
+//!         if let Ok(StreamResponse::TranscriptResponse { is_final: true, .. }) = response {
+//!             finals += 1;
+//!         }
+//!     }
+//!     finals
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let result: StreamResponse = serde_json::from_str(r#"{
+//!     "type": "Results",
+//!     "start": 0.0,
+//!     "duration": 1.0,
+//!     "is_final": true,
+//!     "speech_final": true,
+//!     "from_finalize": false,
+//!     "channel": {"alternatives": [{"transcript": "hello", "words": [], "confidence": 0.9}]},
+//!     "metadata": {
+//!         "request_id": "00000000-0000-4000-8000-000000000000",
+//!         "model_info": {"name": "general", "version": "1", "arch": "nova-3"},
+//!         "model_uuid": "00000000-0000-4000-8000-000000000001"
+//!     },
+//!     "channel_index": [0, 1]
+//! }"#).unwrap();
+//!
+//! let deepgram = MockDeepgram::new(vec![result.clone(), result]);
+//! let mut handle = deepgram.handle();
+//!
+//! assert_eq!(count_finals(&mut handle, vec![0; 320]).await, 2);
+//! assert_eq!(handle.sent_audio(), [vec![0; 320]]);
+//! # }
+//! ```
+//!
+//! [`WebsocketHandle`]: crate::listen::websocket::WebsocketHandle
+
+use std::collections::VecDeque;
+
+use bytes::Bytes;
+use futures::Stream;
+use uuid::Uuid;
+
+use crate::{common::stream_response::StreamResponse, DeepgramError, Result};
+
+/// Creates [`MockStreamHandle`]s that replay a fixed list of responses.
+#[derive(Debug, Clone)]
+pub struct MockDeepgram {
+    responses: Vec<StreamResponse>,
+}
+
+impl MockDeepgram {
+    /// Script the responses every handle will yield, in order.
+    pub fn new(responses: Vec<StreamResponse>) -> Self {
+        Self { responses }
+    }
+
+    /// Open a scripted stream, in place of
+    /// [`WebsocketBuilder::handle`](crate::listen::websocket::WebsocketBuilder::handle).
+    pub fn handle(&self) -> MockStreamHandle {
+        MockStreamHandle {
+            responses: self.responses.iter().cloned().collect(),
+            sent_audio: Vec::new(),
+            closed: false,
+        }
+    }
+}
+
+/// A live transcription stream replaying scripted responses.
+///
+/// Its methods behave like those of
+/// [`WebsocketHandle`](crate::listen::websocket::WebsocketHandle), except that
+/// every response is available right away, whatever audio is sent.
+#[derive(Debug)]
+pub struct MockStreamHandle {
+    responses: VecDeque<StreamResponse>,
+    sent_audio: Vec<Bytes>,
+    closed: bool,
+}
+
+impl MockStreamHandle {
+    /// Record a chunk of audio.
+    ///
+    /// Fails once the stream has been closed.
+    pub async fn send_data(&mut self, data: impl Into<Bytes>) -> Result<()> {
+        self.check_open()?;
+        self.sent_audio.push(data.into());
+        Ok(())
+    }
+
+    /// Record several chunks of audio, each as its own entry of
+    /// [`MockStreamHandle::sent_audio`].
+    pub async fn send_frames(&mut self, frames: impl IntoIterator<Item = Bytes>) -> Result<()> {
+        for frame in frames {
+            self.send_data(frame).await?;
+        }
+        Ok(())
+    }
+
+    /// Accepted and ignored while the stream is open.
+    pub async fn finalize(&mut self) -> Result<()> {
+        self.check_open()
+    }
+
+    /// Accepted and ignored while the stream is open.
+    pub async fn keep_alive(&mut self) -> Result<()> {
+        self.check_open()
+    }
+
+    /// Close the stream. The scripted responses can still be read with
+    /// [`MockStreamHandle::receive`].
+    pub async fn close_stream(&mut self) -> Result<()> {
+        self.closed = true;
+        Ok(())
+    }
+
+    /// Close the stream and return the first scripted
+    /// [`StreamResponse::MetadataResponse`].
+    ///
+    /// The responses scripted before and after it can still be read with
+    /// [`MockStreamHandle::receive`]. Returns `None` if none was scripted.
+    pub async fn close_stream_and_wait(&mut self) -> Result<Option<StreamResponse>> {
+        self.closed = true;
+        let terminal = self.responses.iter().position(StreamResponse::is_metadata);
+        Ok(terminal.and_then(|index| self.responses.remove(index)))
+    }
+
+    /// Return the next scripted response, or `None` once all of them have
+    /// been received.
+    pub async fn receive(&mut self) -> Option<Result<StreamResponse>> {
+        self.responses.pop_front().map(Ok)
+    }
+
+    /// Receive responses like [`MockStreamHandle::receive`], skipping interim
+    /// transcripts.
+    pub fn receive_final(&mut self) -> impl Stream<Item = Result<StreamResponse>> + '_ {
+        futures::stream::unfold(self, |handle| async move {
+            loop {
+                match handle.receive().await? {
+                    Ok(StreamResponse::TranscriptResponse {
+                        is_final: false, ..
+                    }) => continue,
+                    response => return Some((response, handle)),
+                }
+            }
+        })
+    }
+
+    /// Always the nil UUID.
+    pub fn request_id(&self) -> Uuid {
+        Uuid::nil()
+    }
+
+    /// The chunks of audio sent so far, in order.
+    pub fn sent_audio(&self) -> &[Bytes] {
+        &self.sent_audio
+    }
+
+    /// Whether [`MockStreamHandle::close_stream`] has been called.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    fn check_open(&self) -> Result<()> {
+        if self.closed {
+            return Err(DeepgramError::InternalClientError(anyhow::anyhow!(
+                "the stream has been closed"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::MockDeepgram;
+    use crate::{common::stream_response::StreamResponse, DeepgramError};
+
+    fn result(transcript: &str, is_final: bool) -> StreamResponse {
+        serde_json::from_str(&format!(
+            r#"{{
+                "type": "Results",
+                "start": 0.0,
+                "duration": 1.0,
+                "is_final": {is_final},
+                "speech_final": {is_final},
+                "from_finalize": false,
+                "channel": {{"alternatives": [{{"transcript": "{transcript}", "words": [], "confidence": 0.9}}]}},
+                "metadata": {{
+                    "request_id": "00000000-0000-4000-8000-000000000000",
+                    "model_info": {{"name": "general", "version": "1", "arch": "nova-3"}},
+                    "model_uuid": "00000000-0000-4000-8000-000000000001"
+                }},
+                "channel_index": [0, 1]
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    fn terminal() -> StreamResponse {
+        StreamResponse::MetadataResponse {
+            type_field: "Metadata".to_string(),
+            transaction_key: "deprecated".to_string(),
+            request_id: "00000000-0000-4000-8000-000000000000".to_string(),
+            sha256: String::new(),
+            created: "2025-01-01T00:00:00.000Z".to_string(),
+            duration: 1.0,
+            channels: 1,
+            models: Vec::new(),
+            model_info: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_responses_and_records_audio() {
+        let deepgram = MockDeepgram::new(vec![
+            result("hel", false),
+            result("hello", true),
+            terminal(),
+        ]);
+        let mut handle = deepgram.handle();
+
+        handle.send_data(vec![1, 2]).await.unwrap();
+        handle.finalize().await.unwrap();
+        handle.send_data(&b"\x03"[..]).await.unwrap();
+        assert!(matches!(
+            handle.close_stream_and_wait().await.unwrap(),
+            Some(StreamResponse::MetadataResponse { .. })
+        ));
+        assert!(handle.is_closed());
+
+        let responses: Vec<_> = handle.receive_final().collect().await;
+        assert_eq!(responses.len(), 1);
+        assert!(matches!(
+            responses[0],
+            Ok(StreamResponse::TranscriptResponse { is_final: true, .. })
+        ));
+        assert!(handle.receive().await.is_none());
+        assert_eq!(handle.sent_audio(), [vec![1, 2], vec![3]]);
+
+        assert!(matches!(
+            handle.send_data(vec![4]).await,
+            Err(DeepgramError::InternalClientError(_))
+        ));
+
+        // Each handle replays the whole script.
+        let mut handle = deepgram.handle();
+        assert!(matches!(
+            handle.receive().await,
+            Some(Ok(StreamResponse::TranscriptResponse {
+                is_final: false,
+                ..
+            }))
+        ));
+        assert!(handle.sent_audio().is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+//! Websocket connection helpers shared by the streaming APIs.
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use http::{HeaderMap, Request};
+use serde::de::DeserializeOwned;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tungstenite::{handshake::client::Response, protocol::WebSocketConfig};
+use url::Url;
+use uuid::Uuid;
+
+#[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+use crate::TungsteniteError;
+use crate::{DeepgramError, Result};
+
+/// Add the client's custom headers to a websocket upgrade request, without
+/// replacing any header the handshake or authentication already set.
+pub(crate) fn add_custom_headers(request: &mut HeaderMap, custom: &HeaderMap) {
+    for name in custom.keys() {
+        if !request.contains_key(name) {
+            for value in custom.get_all(name) {
+                request.append(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Parse a text message received from Deepgram, keeping its text in the
+/// error if it cannot be parsed.
+pub(crate) fn parse_message<T: DeserializeOwned>(raw: &str) -> Result<T> {
+    serde_json::from_str(raw).map_err(|source| DeepgramError::DeserializationError {
+        source,
+        raw: raw.to_string(),
+    })
+}
+
+/// Open a websocket connection, tunnelling through `proxy` if one is set.
+///
+/// Without a `config`, tungstenite's defaults apply. With a `timeout`, the
+/// connection fails with [`DeepgramError::ConnectTimeout`] if it is not open
+/// in time.
+pub(crate) async fn connect(
+    request: Request<()>,
+    proxy: Option<&Url>,
+    config: Option<WebSocketConfig>,
+    timeout: Option<Duration>,
+) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, dial(request, proxy, config))
+            .await
+            .map_err(|_| DeepgramError::ConnectTimeout(timeout))?,
+        None => dial(request, proxy, config).await,
+    }
+}
+
+async fn dial(
+    request: Request<()>,
+    proxy: Option<&Url>,
+    config: Option<WebSocketConfig>,
+) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> {
+    let Some(proxy) = proxy else {
+        return Ok(tokio_tungstenite::connect_async_with_config(request, config, false).await?);
+    };
+
+    let uri = request.uri();
+    let host = uri.host().ok_or(DeepgramError::InvalidUrl)?;
+    let port = uri
+        .port_u16()
+        .unwrap_or(if uri.scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        });
+    let stream = crate::proxy::tunnel(proxy, host, port).await?;
+
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    let connection =
+        tokio_tungstenite::client_async_tls_with_config(request, stream, config, None).await?;
+    // Without a TLS backend only `ws://` URLs can be reached.
+    #[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+    let connection = {
+        if request.uri().scheme_str() == Some("wss") {
+            return Err(
+                TungsteniteError::Url(tungstenite::error::UrlError::TlsFeatureNotEnabled).into(),
+            );
+        }
+        tokio_tungstenite::client_async_with_config(request, MaybeTlsStream::Plain(stream), config)
+            .await?
+    };
+    Ok(connection)
+}
+
+/// The request ID Deepgram sent in the `dg-request-id` header of the
+/// websocket upgrade response.
+///
+/// Servers that don't send one, such as some self-hosted deployments and
+/// proxies, get the nil UUID rather than failing the connection.
+pub(crate) fn request_id(upgrade_response: &Response) -> Result<Uuid> {
+    let Some(header) = upgrade_response.headers().get("dg-request-id") else {
+        return Ok(Uuid::nil());
+    };
+
+    header
+        .to_str()
+        .ok()
+        .and_then(|header| Uuid::parse_str(header).ok())
+        .ok_or(DeepgramError::UnexpectedServerResponse(anyhow!(
+            "Received malformed request ID in websocket upgrade headers"
+        )))
+}